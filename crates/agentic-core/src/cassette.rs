@@ -0,0 +1,165 @@
+//! Record and replay of model traffic for reproducing bad outputs.
+//!
+//! In record mode every successful reply from `call_local_model` and from the
+//! cloud chain is appended to a JSON cassette file. In replay mode those
+//! replies are served back instead of contacting any backend, so a cassette
+//! attached to a bug report reproduces the exact parse failure elsewhere.
+//! Startup validation is skipped while replaying, since the backends that were
+//! recorded need not exist on the replaying machine.
+
+use crate::settings::{CassetteMode, CassetteSettings};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+const CASSETTE_VERSION: u32 = 1;
+
+/// Which side of the pipeline answered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    Local,
+    Cloud,
+}
+
+/// One prompt and the raw text the model returned for it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Exchange {
+    pub backend: Backend,
+    pub model: String,
+    pub prompt: String,
+    pub response: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Cassette {
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub exchanges: Vec<Exchange>,
+}
+
+impl Cassette {
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Cannot read cassette {}: {}", path.display(), e))?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// A cassette being played back, remembering which exchanges were served
+#[derive(Debug)]
+pub struct Player {
+    exchanges: Vec<Exchange>,
+    played: Vec<bool>,
+}
+
+impl Player {
+    pub fn new(cassette: Cassette) -> Self {
+        let played = vec![false; cassette.exchanges.len()];
+        Self {
+            exchanges: cassette.exchanges,
+            played,
+        }
+    }
+
+    /// The matching unplayed exchange, or else the next unplayed one for the backend
+    ///
+    /// Falling back to recording order keeps replay working when the model was
+    /// renamed or a prompt template changed slightly since recording.
+    pub fn next_reply(&mut self, backend: Backend, model: &str, prompt: &str) -> Option<String> {
+        let unplayed = |index: &usize| !self.played[*index];
+        let candidates = (0..self.exchanges.len())
+            .filter(unplayed)
+            .filter(|&i| self.exchanges[i].backend == backend);
+        let exact = candidates
+            .clone()
+            .find(|&i| self.exchanges[i].model == model && self.exchanges[i].prompt == prompt);
+        let index = exact.or_else(|| candidates.clone().next())?;
+        self.played[index] = true;
+        Some(self.exchanges[index].response.clone())
+    }
+}
+
+enum State {
+    Recording { path: PathBuf, cassette: Cassette },
+    Replaying(Player),
+}
+
+static CASSETTE: OnceLock<Mutex<Option<State>>> = OnceLock::new();
+
+fn global_state() -> &'static Mutex<Option<State>> {
+    CASSETTE.get_or_init(|| Mutex::new(None))
+}
+
+/// Start recording or replaying as configured; replay fails if the file is unreadable
+pub fn configure(settings: &CassetteSettings) -> Result<()> {
+    let path = PathBuf::from(&settings.path);
+    let state = match settings.mode {
+        CassetteMode::Off => None,
+        CassetteMode::Record => Some(State::Recording {
+            path,
+            cassette: Cassette {
+                version: CASSETTE_VERSION,
+                exchanges: Vec::new(),
+            },
+        }),
+        CassetteMode::Replay => Some(State::Replaying(Player::new(Cassette::load_from(&path)?))),
+    };
+    if let Ok(mut global) = global_state().lock() {
+        *global = state;
+    }
+    Ok(())
+}
+
+/// Whether replies come from a cassette, so no backend needs to be reachable
+pub fn replaying() -> bool {
+    global_state()
+        .lock()
+        .map(|state| matches!(*state, Some(State::Replaying(_))))
+        .unwrap_or(false)
+}
+
+/// The recorded reply for this request, or `None` when not replaying
+pub fn replay(backend: Backend, model: &str, prompt: &str) -> Option<Result<String>> {
+    let mut state = global_state().lock().ok()?;
+    let Some(State::Replaying(player)) = state.as_mut() else {
+        return None;
+    };
+    Some(player.next_reply(backend, model, prompt).ok_or_else(|| {
+        anyhow::anyhow!(
+            "The cassette has no recorded {:?} reply left for model '{}'",
+            backend,
+            model
+        )
+    }))
+}
+
+/// Append a reply to the cassette file when recording
+pub fn record(backend: Backend, model: &str, prompt: &str, response: &str) {
+    let Ok(mut state) = global_state().lock() else {
+        return;
+    };
+    if let Some(State::Recording { path, cassette }) = state.as_mut() {
+        cassette.exchanges.push(Exchange {
+            backend,
+            model: model.to_string(),
+            prompt: prompt.to_string(),
+            response: response.to_string(),
+        });
+        // Rewrite the whole file so it stays valid if the app is killed
+        if let Err(e) = cassette.save_to(path) {
+            std::fs::write("/tmp/debug_cassette.txt", e.to_string()).ok();
+        }
+    }
+}
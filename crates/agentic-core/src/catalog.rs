@@ -0,0 +1,174 @@
+//! On-disk cache of the OpenRouter model catalogue.
+//!
+//! The `/models` list is large and changes slowly, so it is downloaded at most
+//! once per TTL and shared by validation, the model picker and pricing
+//! lookups. A stale copy is still served when the network is unavailable.
+
+use crate::mock;
+use crate::model_filter::price_per_token;
+use crate::models::{ModelPricing, ModelValidator, OpenRouterModel};
+use crate::paths;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const CATALOG_FILE: &str = "openrouter_models.json";
+
+/// Default time before the cached catalogue is downloaded again
+pub const DEFAULT_CATALOG_TTL_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelCatalog {
+    /// Unix timestamp (seconds) of the download
+    pub fetched_at: u64,
+    pub models: Vec<OpenRouterModel>,
+}
+
+impl ModelCatalog {
+    pub fn new(models: Vec<OpenRouterModel>) -> Self {
+        Self {
+            fetched_at: now_secs(),
+            models,
+        }
+    }
+
+    /// Default location inside the user cache directory
+    pub fn default_path() -> Option<PathBuf> {
+        paths::cache_dir().map(|dir| dir.join(CATALOG_FILE))
+    }
+
+    /// Read a cached catalogue; missing or corrupt files yield `None`
+    pub fn load_from(path: &Path) -> Option<Self> {
+        let contents = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// How long ago the catalogue was downloaded
+    pub fn age(&self) -> Duration {
+        age_since(self.fetched_at)
+    }
+
+    pub fn is_fresh(&self, ttl: Duration) -> bool {
+        self.age() < ttl
+    }
+
+    pub fn find(&self, id: &str) -> Option<&OpenRouterModel> {
+        self.models.iter().find(|model| model.id == id)
+    }
+
+    pub fn pricing(&self, id: &str) -> Option<&ModelPricing> {
+        self.find(id).map(|model| &model.pricing)
+    }
+}
+
+/// Return the cached catalogue, downloading it when stale, missing or forced
+///
+/// If the download fails, a stale cached copy is returned instead so the model
+/// list stays browsable offline.
+pub async fn load_catalog(
+    validator: &ModelValidator,
+    api_key: &str,
+    ttl: Duration,
+    force_refresh: bool,
+) -> Result<ModelCatalog> {
+    let path = ModelCatalog::default_path();
+    let cached = path.as_deref().and_then(ModelCatalog::load_from);
+
+    if let Some(catalog) = &cached {
+        if !force_refresh && catalog.is_fresh(ttl) {
+            return Ok(catalog.clone());
+        }
+    }
+
+    match validator.fetch_openrouter_models(api_key).await {
+        Ok(models) => {
+            let catalog = ModelCatalog::new(models);
+            if let Some(path) = &path {
+                // A read-only cache dir only costs us the next download
+                catalog.save_to(path).ok();
+            }
+            Ok(catalog)
+        }
+        Err(e) => cached.ok_or(e),
+    }
+}
+
+/// Catalogue for the cloud model picker
+///
+/// A chain of only `mock/` models stays offline: the mock models are listed
+/// ahead of whatever real catalogue is cached. Otherwise the real catalogue is
+/// loaded as usual, with the mock models first while the mock provider is on.
+pub async fn load_picker_catalog(
+    validator: &ModelValidator,
+    api_key: &str,
+    chain: &[String],
+    ttl: Duration,
+    force_refresh: bool,
+) -> Result<ModelCatalog> {
+    let mock_chain = !chain.is_empty() && chain.iter().all(|model| mock::is_mock_model(model));
+    let mut catalog = if mock_chain {
+        ModelCatalog::default_path()
+            .as_deref()
+            .and_then(ModelCatalog::load_from)
+            .unwrap_or_else(|| ModelCatalog::new(Vec::new()))
+    } else {
+        load_catalog(validator, api_key, ttl, force_refresh).await?
+    };
+    if mock_chain || mock::enabled() {
+        catalog.models.splice(0..0, mock::cloud_models());
+    }
+    Ok(catalog)
+}
+
+/// Pricing for a model from the cached catalogue, without touching the network
+pub fn cached_pricing(id: &str) -> Option<ModelPricing> {
+    let path = ModelCatalog::default_path()?;
+    ModelCatalog::load_from(&path)?.pricing(id).cloned()
+}
+
+/// Estimated request cost in USD, when the model has fixed per-token pricing
+pub fn estimate_cost_usd(
+    pricing: &ModelPricing,
+    prompt_tokens: u32,
+    completion_tokens: u32,
+) -> Option<f64> {
+    let prompt = price_per_token(&pricing.prompt)?;
+    let completion = price_per_token(&pricing.completion)?;
+    Some(prompt * prompt_tokens as f64 + completion * completion_tokens as f64)
+}
+
+/// Time elapsed since a `fetched_at` timestamp
+pub fn age_since(fetched_at: u64) -> Duration {
+    Duration::from_secs(now_secs().saturating_sub(fetched_at))
+}
+
+/// Human-readable age such as `5m ago` or `3h ago`, for status lines
+pub fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    if secs < 60 {
+        "just now".to_string()
+    } else if secs < 60 * 60 {
+        format!("{}m ago", secs / 60)
+    } else if secs < 24 * 60 * 60 {
+        format!("{}h ago", secs / (60 * 60))
+    } else {
+        format!("{}d ago", secs / (24 * 60 * 60))
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -1,9 +1,29 @@
-use crate::models::AtomicNote;
+use crate::cassette::{self, Backend};
+use crate::mock;
+use crate::models::{call_local_model, AtomicNote};
+use crate::repair::parse_with_repair;
+use crate::retry::{parse_retry_after, retry_with_backoff, RetryPolicy, Retryable};
+use crate::settings::GenerationSettings;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 use thiserror::Error;
 
+// Process-wide switch so local-only mode can guarantee nothing leaves the machine
+static CLOUD_EGRESS_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Allow or forbid every request to the cloud provider
+pub fn set_cloud_egress_enabled(enabled: bool) {
+    CLOUD_EGRESS_ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+/// Whether requests to the cloud provider are currently allowed
+pub fn cloud_egress_enabled() -> bool {
+    CLOUD_EGRESS_ENABLED.load(Ordering::SeqCst)
+}
+
 #[derive(Debug, Error)]
 pub enum CloudError {
     #[error("The cloud provider rejected the API key. It might have expired or been disabled.")]
@@ -12,11 +32,99 @@ pub enum CloudError {
     #[error("The cloud model returned a response that could not be understood.")]
     ParseError,
 
+    #[error("The cloud provider is rate limiting requests.")]
+    RateLimited { retry_after: Option<Duration> },
+
+    #[error("The upstream model provider failed: {status}: {text}")]
+    UpstreamError { status: u16, text: String },
+
+    #[error("The cloud account has insufficient credits for this request.")]
+    InsufficientCredits,
+
+    #[error("The request exceeds the model's context length: {text}")]
+    ContextLengthExceeded { text: String },
+
+    #[error("The cloud provider did not respond in time.")]
+    Timeout,
+
+    #[error("Cloud requests are disabled in local-only mode.")]
+    EgressDisabled,
+
+    #[error("The cloud model is unavailable: {text}")]
+    ModelUnavailable { text: String },
+
+    #[error("No cloud model is configured.")]
+    NoModel,
+
     #[error("The cloud provider returned an unexpected error: {status}: {text}")]
     ApiError { status: u16, text: String },
 
     #[error(transparent)]
-    RequestError(#[from] reqwest::Error),
+    RequestError(reqwest::Error),
+}
+
+impl From<reqwest::Error> for CloudError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            CloudError::Timeout
+        } else {
+            CloudError::RequestError(error)
+        }
+    }
+}
+
+impl Retryable for CloudError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            CloudError::RateLimited { .. } | CloudError::UpstreamError { .. } => true,
+            CloudError::Timeout => true,
+            CloudError::RequestError(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            CloudError::RateLimited { retry_after } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+impl CloudError {
+    /// Classify a failed OpenRouter response by status code and error text
+    pub fn from_status(status: u16, retry_after: Option<Duration>, text: String) -> Self {
+        let lowered = text.to_lowercase();
+        match status {
+            401 => CloudError::ApiKey,
+            402 => CloudError::InsufficientCredits,
+            408 => CloudError::Timeout,
+            429 => CloudError::RateLimited { retry_after },
+            400 | 413
+                if lowered.contains("context length")
+                    || lowered.contains("context_length")
+                    || lowered.contains("maximum context")
+                    || lowered.contains("too many tokens") =>
+            {
+                CloudError::ContextLengthExceeded { text }
+            }
+            404 => CloudError::ModelUnavailable { text },
+            500 | 502 | 503 | 504 => CloudError::UpstreamError { status, text },
+            _ => CloudError::ApiError { status, text },
+        }
+    }
+
+    /// Whether the next model in the fallback chain might succeed
+    pub fn should_fall_back(&self) -> bool {
+        self.is_retryable() || matches!(self, CloudError::ModelUnavailable { .. })
+    }
+}
+
+/// A synthesized note and the model in the chain that produced it
+#[derive(Debug, Clone)]
+pub struct CloudSynthesis {
+    pub note: AtomicNote,
+    pub model: String,
 }
 
 const SYNTHESIZER_PROMPT: &str = r#"You are an expert-level AI Synthesizer. Your task is to answer the user's prompt by generating a concise, "atomic note" of knowledge.
@@ -39,6 +147,11 @@ USER PROMPT:
 {prompt}
 "#;
 
+const ATOMIC_NOTE_FORMAT: &str = r#"{
+  "header_tags": ["keyword1", "keyword2", "keyword3"],
+  "body_text": "Your concise, 3-4 sentence summary goes here."
+}"#;
+
 #[derive(Serialize)]
 struct ResponseFormat {
     r#type: String,
@@ -48,7 +161,14 @@ struct ResponseFormat {
 struct OpenRouterRequest<'a> {
     model: String,
     messages: Vec<ChatMessage<'a>>,
-    max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
     response_format: ResponseFormat,
 }
 
@@ -63,6 +183,18 @@ struct OpenRouterResponse {
     choices: Vec<Choice>,
 }
 
+/// OpenRouter can report provider failures inside a 200 response body
+#[derive(Deserialize)]
+struct OpenRouterErrorResponse {
+    error: OpenRouterErrorBody,
+}
+
+#[derive(Deserialize)]
+struct OpenRouterErrorBody {
+    code: Option<u16>,
+    message: String,
+}
+
 #[derive(Deserialize)]
 struct Choice {
     message: Message,
@@ -73,25 +205,198 @@ struct Message {
     content: String,
 }
 
+/// Synthesize a note, falling back along `models` on rate limits and outages
 pub async fn call_cloud_model(
     api_key: &str,
-    model: &str,
+    models: &[String],
     prompt: &str,
+    repair_rounds: u32,
+    params: &GenerationSettings,
+) -> Result<CloudSynthesis, CloudError> {
+    if !cloud_egress_enabled() {
+        return Err(CloudError::EgressDisabled);
+    }
+
+    let client = Client::builder().timeout(params.timeout()).build()?;
+
+    let synthesizer_prompt = SYNTHESIZER_PROMPT.replace("{prompt}", prompt);
+
+    // Debug: Write the synthesis prompt to see what we're sending
+    std::fs::write("/tmp/debug_synthesis_prompt.txt", &synthesizer_prompt).ok();
+
+    let (note, model) = with_model_fallback(models, |model| {
+        synthesize_with_model(
+            &client,
+            api_key,
+            model,
+            &synthesizer_prompt,
+            repair_rounds,
+            params,
+        )
+    })
+    .await?;
+
+    Ok(CloudSynthesis {
+        note,
+        model: model.to_string(),
+    })
+}
+
+async fn synthesize_with_model(
+    client: &Client,
+    api_key: &str,
+    model: &str,
+    synthesizer_prompt: &str,
+    repair_rounds: u32,
+    params: &GenerationSettings,
 ) -> Result<AtomicNote, CloudError> {
-    let client = Client::builder().timeout(Duration::from_secs(30)).build()?;
+    let message_content =
+        send_synthesis_request(client, api_key, model, synthesizer_prompt, params).await?;
 
+    // Try multiple parsing strategies for cloud model response
+    parse_with_repair(
+        "cloud",
+        message_content,
+        repair_rounds,
+        ATOMIC_NOTE_FORMAT,
+        parse_atomic_note_with_fallbacks,
+        |repair_prompt| async move {
+            send_synthesis_request(client, api_key, model, &repair_prompt, params).await
+        },
+    )
+    .await?
+    .map_err(|_| CloudError::ParseError)
+}
+
+/// Send a prompt along the model chain and return the raw reply (cloud-only orchestration)
+pub async fn call_cloud_completion(
+    api_key: &str,
+    models: &[String],
+    prompt: &str,
+    params: &GenerationSettings,
+) -> Result<String, CloudError> {
+    if !cloud_egress_enabled() {
+        return Err(CloudError::EgressDisabled);
+    }
+
+    let client = Client::builder().timeout(params.timeout()).build()?;
+
+    let (reply, _) = with_model_fallback(models, |model| {
+        send_synthesis_request(&client, api_key, model, prompt, params)
+    })
+    .await?;
+    Ok(reply)
+}
+
+/// Try each model in turn until one answers or fails with a non-transient error
+async fn with_model_fallback<'m, T, F, Fut>(
+    models: &'m [String],
+    mut attempt: F,
+) -> Result<(T, &'m str), CloudError>
+where
+    F: FnMut(&'m str) -> Fut,
+    Fut: Future<Output = Result<T, CloudError>>,
+{
+    let mut last_error = CloudError::NoModel;
+    for (index, model) in models.iter().enumerate() {
+        match attempt(model).await {
+            Ok(value) => return Ok((value, model)),
+            Err(e) if e.should_fall_back() && index + 1 < models.len() => {
+                let debug_info = format!(
+                    "Model {} failed, falling back to {}\nError: {}",
+                    model,
+                    models[index + 1],
+                    e
+                );
+                std::fs::write("/tmp/debug_fallback.txt", debug_info).ok();
+                last_error = e;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Err(last_error)
+}
+
+/// Run the synthesizer prompt on the local model (local-only mode)
+pub async fn call_local_synthesis(
+    endpoint: &str,
+    model: &str,
+    prompt: &str,
+    repair_rounds: u32,
+    params: &GenerationSettings,
+) -> Result<AtomicNote, anyhow::Error> {
     let synthesizer_prompt = SYNTHESIZER_PROMPT.replace("{prompt}", prompt);
 
     // Debug: Write the synthesis prompt to see what we're sending
     std::fs::write("/tmp/debug_synthesis_prompt.txt", &synthesizer_prompt).ok();
 
+    let message_content = call_local_model(endpoint, model, &synthesizer_prompt, params).await?;
+
+    parse_with_repair(
+        "local_synthesis",
+        message_content,
+        repair_rounds,
+        ATOMIC_NOTE_FORMAT,
+        parse_atomic_note_with_fallbacks,
+        |repair_prompt| async move {
+            call_local_model(endpoint, model, &repair_prompt, params).await
+        },
+    )
+    .await?
+    .map_err(|error| {
+        anyhow::anyhow!(
+            "Local model '{}' did not return a parseable atomic note: {}",
+            model,
+            error
+        )
+    })
+}
+
+async fn send_synthesis_request(
+    client: &Client,
+    api_key: &str,
+    model: &str,
+    prompt: &str,
+    params: &GenerationSettings,
+) -> Result<String, CloudError> {
+    if let Some(reply) = cassette::replay(Backend::Cloud, model, prompt) {
+        return reply.map_err(|e| CloudError::ModelUnavailable {
+            text: e.to_string(),
+        });
+    }
+
+    let reply = retry_with_backoff(&RetryPolicy::default(), "cloud", || {
+        send_synthesis_attempt(client, api_key, model, prompt, params)
+    })
+    .await?;
+    cassette::record(Backend::Cloud, model, prompt, &reply);
+    Ok(reply)
+}
+
+async fn send_synthesis_attempt(
+    client: &Client,
+    api_key: &str,
+    model: &str,
+    prompt: &str,
+    params: &GenerationSettings,
+) -> Result<String, CloudError> {
+    if mock::is_mock_model(model) {
+        return mock::backend()
+            .reply(prompt)
+            .await
+            .map_err(|e| CloudError::from_status(e.status, None, e.text));
+    }
+
     let request_body = OpenRouterRequest {
         model: model.to_string(),
         messages: vec![ChatMessage {
             role: "user".to_string(),
-            content: &synthesizer_prompt,
+            content: prompt,
         }],
-        max_tokens: 1024,
+        max_tokens: params.max_tokens,
+        temperature: params.temperature,
+        top_p: params.top_p,
+        seed: params.seed,
         response_format: ResponseFormat {
             r#type: "json_object".to_string(),
         },
@@ -107,14 +412,13 @@ pub async fn call_cloud_model(
 
     if !response.status().is_success() {
         let status = response.status();
-        if status == 401 {
-            return Err(CloudError::ApiKey);
-        }
+        let retry_after = parse_retry_after(response.headers());
         let error_text = response.text().await.unwrap_or_default();
-        return Err(CloudError::ApiError {
-            status: status.as_u16(),
-            text: error_text,
-        });
+        return Err(CloudError::from_status(
+            status.as_u16(),
+            retry_after,
+            error_text,
+        ));
     }
 
     let response_text = response.text().await?;
@@ -125,6 +429,13 @@ pub async fn call_cloud_model(
     let openrouter_response: OpenRouterResponse = match serde_json::from_str(&response_text) {
         Ok(res) => res,
         Err(e) => {
+            if let Ok(res) = serde_json::from_str::<OpenRouterErrorResponse>(&response_text) {
+                return Err(CloudError::from_status(
+                    res.error.code.unwrap_or(502),
+                    None,
+                    res.error.message,
+                ));
+            }
             let debug_info = format!(
                 "Cloud API Response Parse Error: {}\nRaw Response: {}",
                 e, response_text
@@ -134,17 +445,15 @@ pub async fn call_cloud_model(
         }
     };
 
-    let message_content = openrouter_response
+    openrouter_response
         .choices
-        .first()
-        .map(|choice| &choice.message.content)
-        .ok_or(CloudError::ParseError)?;
-
-    // Try multiple parsing strategies for cloud model response
-    parse_atomic_note_with_fallbacks(message_content)
+        .into_iter()
+        .next()
+        .map(|choice| choice.message.content)
+        .ok_or(CloudError::ParseError)
 }
 
-fn parse_atomic_note_with_fallbacks(message_content: &str) -> Result<AtomicNote, CloudError> {
+fn parse_atomic_note_with_fallbacks(message_content: &str) -> Result<AtomicNote, String> {
     // Strategy 1: Extract from markdown code blocks
     let clean_content = extract_json_from_cloud_markdown(message_content);
 
@@ -152,9 +461,10 @@ fn parse_atomic_note_with_fallbacks(message_content: &str) -> Result<AtomicNote,
     std::fs::write("/tmp/debug_synthesis_json.txt", clean_content).ok();
 
     // Strategy 2: Try direct JSON parsing
-    if let Ok(note) = serde_json::from_str::<AtomicNote>(clean_content) {
-        return Ok(note);
-    }
+    let json_error = match serde_json::from_str::<AtomicNote>(clean_content) {
+        Ok(note) => return Ok(note),
+        Err(e) => e.to_string(),
+    };
 
     // Strategy 3: Try to find just the JSON object
     if let Some(json_start) = clean_content.find("{") {
@@ -179,7 +489,10 @@ fn parse_atomic_note_with_fallbacks(message_content: &str) -> Result<AtomicNote,
     );
     std::fs::write("/tmp/debug_synthesis_parse_failure.txt", &debug_info).ok();
 
-    Err(CloudError::ParseError)
+    Err(format!(
+        "Expected a JSON object with header_tags and body_text: {}",
+        json_error
+    ))
 }
 
 fn extract_json_from_cloud_markdown(content: &str) -> &str {
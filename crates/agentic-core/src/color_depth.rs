@@ -0,0 +1,268 @@
+//! Terminal color support detection and palette downsampling.
+//!
+//! Themes are written in 24-bit RGB. Terminals that cannot show it (the
+//! Linux console, tmux without truecolor, most SSH sessions) get the nearest
+//! 256- or 16-color equivalent, and `NO_COLOR` turns color off entirely.
+
+use ratatui::style::Color;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// How many colors the terminal can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorDepth {
+    /// 24-bit RGB
+    #[default]
+    TrueColor,
+    /// The xterm 256-color palette
+    Ansi256,
+    /// The 16 standard ANSI colors
+    Ansi16,
+    /// Terminal default colors only; emphasis uses bold and reverse video
+    NoColor,
+}
+
+impl ColorDepth {
+    /// Detect from `NO_COLOR`, `COLORTERM`, `TERM` and the terminfo database
+    pub fn detect() -> Self {
+        let var = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+        Self::from_env(
+            var("NO_COLOR").is_some(),
+            var("COLORTERM").as_deref(),
+            var("TERM").as_deref(),
+            var("TERM_PROGRAM").as_deref(),
+        )
+    }
+
+    /// Detection from already-read environment values
+    pub fn from_env(
+        no_color: bool,
+        colorterm: Option<&str>,
+        term: Option<&str>,
+        term_program: Option<&str>,
+    ) -> Self {
+        if no_color {
+            return ColorDepth::NoColor;
+        }
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            return ColorDepth::TrueColor;
+        }
+        // Terminal.app advertises xterm-256color but mangles RGB
+        if term_program == Some("Apple_Terminal") {
+            return ColorDepth::Ansi256;
+        }
+
+        let Some(term) = term else {
+            // No TERM at all, e.g. Windows Terminal, which handles RGB
+            return ColorDepth::TrueColor;
+        };
+        if term == "dumb" {
+            return ColorDepth::NoColor;
+        }
+        if term.contains("truecolor") || term.contains("24bit") || term.ends_with("-direct") {
+            return ColorDepth::TrueColor;
+        }
+        if term.contains("256color") {
+            return ColorDepth::Ansi256;
+        }
+        match terminfo_colors(term) {
+            Some(colors) if colors >= 1 << 24 => ColorDepth::TrueColor,
+            Some(colors) if colors >= 256 => ColorDepth::Ansi256,
+            Some(colors) if colors >= 8 => ColorDepth::Ansi16,
+            Some(_) => ColorDepth::NoColor,
+            // Unknown terminal: 16 colors are the safe assumption
+            None => ColorDepth::Ansi16,
+        }
+    }
+
+    /// The closest color this depth can show
+    pub fn adapt(self, color: Color) -> Color {
+        match (self, color) {
+            (_, Color::Reset) | (ColorDepth::TrueColor, _) => color,
+            (ColorDepth::NoColor, _) => Color::Reset,
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_256(r, g, b)),
+            (ColorDepth::Ansi256, _) => color,
+            (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => nearest_16(r, g, b),
+            (ColorDepth::Ansi16, Color::Indexed(index)) if index < 16 => ANSI_16[index as usize].0,
+            (ColorDepth::Ansi16, Color::Indexed(index)) => {
+                let (r, g, b) = indexed_rgb(index);
+                nearest_16(r, g, b)
+            }
+            (ColorDepth::Ansi16, _) => color,
+        }
+    }
+}
+
+/// The 16 ANSI colors with xterm's default RGB values, by palette index
+const ANSI_16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+/// Map by hue rather than RGB distance, which would turn muted theme colors gray
+fn nearest_16(r: u8, g: u8, b: u8) -> Color {
+    let max = r.max(g).max(b) as f32;
+    let min = r.min(g).min(b) as f32;
+    let range = max - min;
+
+    if max == 0.0 || range / max < 0.25 {
+        let average = (r as u32 + g as u32 + b as u32) / 3;
+        return match average {
+            0..=59 => Color::Black,
+            60..=149 => Color::DarkGray,
+            150..=219 => Color::Gray,
+            _ => Color::White,
+        };
+    }
+
+    let (r, g, b) = (r as f32, g as f32, b as f32);
+    let hue = if max == r {
+        (60.0 * (g - b) / range).rem_euclid(360.0)
+    } else if max == g {
+        120.0 + 60.0 * (b - r) / range
+    } else {
+        240.0 + 60.0 * (r - g) / range
+    };
+    let bright = max >= 200.0;
+    let pick = |normal, light| if bright { light } else { normal };
+    // Yellow is a narrow band; wide green and blue keep sage and mauve apart
+    match hue as u32 {
+        35..=59 => pick(Color::Yellow, Color::LightYellow),
+        60..=164 => pick(Color::Green, Color::LightGreen),
+        165..=199 => pick(Color::Cyan, Color::LightCyan),
+        200..=279 => pick(Color::Blue, Color::LightBlue),
+        280..=329 => pick(Color::Magenta, Color::LightMagenta),
+        _ => pick(Color::Red, Color::LightRed),
+    }
+}
+
+/// Nearest entry of the 6x6x6 color cube or the 24-step gray ramp
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |value: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - value as i32).abs())
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+    let cube_rgb = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray_value = 8 + 10 * gray_step;
+    let gray_rgb = (gray_value, gray_value, gray_value);
+
+    if distance(gray_rgb, (r, g, b)) < distance(cube_rgb, (r, g, b)) {
+        232 + gray_step
+    } else {
+        cube_index as u8
+    }
+}
+
+/// RGB value of an xterm 256-color palette entry
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI_16[index as usize].1,
+        16..=231 => {
+            let cube = index - 16;
+            (
+                CUBE_LEVELS[(cube / 36) as usize],
+                CUBE_LEVELS[(cube / 6 % 6) as usize],
+                CUBE_LEVELS[(cube % 6) as usize],
+            )
+        }
+        _ => {
+            let value = 8 + 10 * (index - 232);
+            (value, value, value)
+        }
+    }
+}
+
+/// The `colors` capability from the compiled terminfo entry for `term`
+fn terminfo_colors(term: &str) -> Option<u32> {
+    let first = term.chars().next()?;
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if let Some(dir) = env::var_os("TERMINFO") {
+        dirs.push(dir.into());
+    }
+    if let Some(home) = env::var_os("HOME") {
+        dirs.push(PathBuf::from(home).join(".terminfo"));
+    }
+    if let Some(list) = env::var_os("TERMINFO_DIRS") {
+        dirs.extend(env::split_paths(&list));
+    }
+    dirs.extend(
+        ["/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo"]
+            .iter()
+            .map(PathBuf::from),
+    );
+
+    dirs.iter()
+        .flat_map(|dir| {
+            // Linux uses the first letter, macOS its hex code
+            [
+                dir.join(first.to_string()).join(term),
+                dir.join(format!("{:x}", first as u32)).join(term),
+            ]
+        })
+        .find_map(|path| fs::read(path).ok())
+        .and_then(|entry| parse_terminfo_colors(&entry))
+}
+
+/// Read `max_colors` (numeric capability 13) from a compiled terminfo entry
+pub fn parse_terminfo_colors(entry: &[u8]) -> Option<u32> {
+    const COLORS_INDEX: usize = 13;
+    let short = |offset: usize| -> Option<i16> {
+        Some(i16::from_le_bytes([
+            *entry.get(offset)?,
+            *entry.get(offset + 1)?,
+        ]))
+    };
+
+    // 0o432: 16-bit numbers; 0o1036: ncurses 6.1 extended 32-bit numbers
+    let number_size = match short(0)? {
+        0o432 => 2,
+        0o1036 => 4,
+        _ => return None,
+    };
+    let names_size = short(2)? as usize;
+    let bool_count = short(4)? as usize;
+    let number_count = short(6)? as usize;
+    if number_count <= COLORS_INDEX {
+        return None;
+    }
+
+    // Numbers start on an even byte after the header, names and booleans
+    let mut offset = 12 + names_size + bool_count;
+    offset += offset % 2;
+    offset += COLORS_INDEX * number_size;
+    let value = if number_size == 2 {
+        short(offset)? as i32
+    } else {
+        i32::from_le_bytes(entry.get(offset..offset + 4)?.try_into().ok()?)
+    };
+    // -1 and -2 mean absent or cancelled
+    u32::try_from(value).ok()
+}
@@ -0,0 +1,44 @@
+//! Crash reports written when the TUI panics.
+//!
+//! A report holds the panic message, location and backtrace, never the
+//! settings, so API keys stay out of files people attach to bug reports.
+
+use crate::paths;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const CRASH_DIR: &str = "crashes";
+
+/// Crash reports folder inside the user data directory
+pub fn crash_dir() -> Option<PathBuf> {
+    paths::data_dir().map(|dir| dir.join(CRASH_DIR))
+}
+
+/// Write a report into `dir` and return its path
+pub fn write_report(
+    dir: &Path,
+    version: &str,
+    message: &str,
+    backtrace: &str,
+) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let now = chrono::Local::now();
+    let path = dir.join(format!(
+        "crash-{}-{}.txt",
+        now.format("%Y%m%d-%H%M%S"),
+        std::process::id()
+    ));
+    let report = format!(
+        "Ruixen {} crashed at {}\nPlatform: {} {}\n\n{}\n\nBacktrace:\n{}\n",
+        version,
+        now.to_rfc3339(),
+        env::consts::OS,
+        env::consts::ARCH,
+        message,
+        backtrace
+    );
+    fs::write(&path, report)?;
+    Ok(path)
+}
@@ -0,0 +1,187 @@
+//! Per-backend health checks behind the `/status` panel.
+//!
+//! Runs the same steps as `Settings::validate_local_only` and
+//! `Settings::validate_cloud_only`, but keeps the timing and exact error of
+//! each step instead of collapsing them into a `ValidationError`.
+
+use crate::mock;
+use crate::models::{KeyInfo, LocalProvider, ModelValidator};
+use crate::settings::Settings;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// Outcome of one validation step
+#[derive(Debug, Clone)]
+pub struct HealthStep {
+    pub label: String,
+    pub elapsed: Duration,
+    /// The failure reason, or `None` when the step passed
+    pub error: Option<String>,
+}
+
+impl HealthStep {
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Everything `/status` shows about one backend
+#[derive(Debug, Clone, Default)]
+pub struct BackendHealth {
+    /// Endpoint or host that was checked
+    pub target: String,
+    /// Detected local provider; `None` for the cloud
+    pub provider: Option<LocalProvider>,
+    /// Round trip of a single request, when the backend answered at all
+    pub latency: Option<Duration>,
+    pub model: String,
+    /// Whether Ollama holds the model in memory; `None` when unknown
+    pub model_loaded: Option<bool>,
+    pub key_info: Option<KeyInfo>,
+    pub steps: Vec<HealthStep>,
+}
+
+impl BackendHealth {
+    pub fn reachable(&self) -> bool {
+        self.latency.is_some()
+    }
+
+    /// Usable end to end; a cloud fallback may have covered earlier failures
+    pub fn healthy(&self) -> bool {
+        self.steps.last().is_some_and(HealthStep::passed)
+    }
+
+    /// Run `check` and record it as a step, keeping its value when it passed
+    async fn step<T, Fut>(&mut self, label: impl Into<String>, check: Fut) -> Option<T>
+    where
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        let started = Instant::now();
+        let result = check.await;
+        let elapsed = started.elapsed();
+        let (value, error) = match result {
+            Ok(value) => (Some(value), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        self.steps.push(HealthStep {
+            label: label.into(),
+            elapsed,
+            error,
+        });
+        value
+    }
+
+    /// Take the latency from the first step, if it got an answer; only for
+    /// steps that make exactly one request
+    fn record_latency(&mut self) {
+        if let Some(first) = self.steps.first().filter(|step| step.passed()) {
+            self.latency = Some(first.elapsed);
+        }
+    }
+}
+
+/// Check the local endpoint: reachability, model, memory and generation
+pub async fn check_local(settings: &Settings) -> BackendHealth {
+    let validator = ModelValidator::new();
+    let endpoint = settings.endpoint.as_str();
+    let model = settings.local_model.as_str();
+    let mut health = BackendHealth {
+        target: endpoint.to_string(),
+        model: model.to_string(),
+        ..Default::default()
+    };
+
+    let listed = health
+        .step("List models", validator.fetch_local_models(endpoint))
+        .await;
+    if listed.is_none() {
+        return health;
+    }
+
+    let provider = validator.detect_provider_type(endpoint).await;
+    health.provider = Some(provider.clone());
+
+    // Listing also probed for the provider; with it known, time one request
+    let started = Instant::now();
+    if validator.fetch_local_models(endpoint).await.is_ok() {
+        health.latency = Some(started.elapsed());
+    }
+
+    if health
+        .step(
+            "Find model",
+            validator.validate_local_endpoint(endpoint, model),
+        )
+        .await
+        .is_none()
+    {
+        return health;
+    }
+
+    // Check before generating, which would load the model itself
+    if provider == LocalProvider::Ollama {
+        if let Ok(loaded) = validator.fetch_loaded_models(endpoint).await {
+            health.model_loaded = Some(loaded.iter().any(|name| name == model));
+        }
+    }
+
+    health
+        .step(
+            "Test generation",
+            validator.test_local_generation(endpoint, model),
+        )
+        .await;
+    health
+}
+
+/// Check OpenRouter: the API key, then each model in the chain until one works
+pub async fn check_cloud(settings: &Settings) -> BackendHealth {
+    let validator = ModelValidator::new();
+    let api_key = settings.api_key.as_str();
+    let mut health = BackendHealth {
+        target: "openrouter.ai".to_string(),
+        model: settings.cloud_model.to_string(),
+        ..Default::default()
+    };
+
+    // Mock models need no key, so don't fail on a placeholder
+    let key_check = async {
+        if settings
+            .cloud_model
+            .models()
+            .iter()
+            .all(|m| mock::is_mock_model(m))
+        {
+            Ok(mock::key_info())
+        } else {
+            validator.fetch_openrouter_key_info(api_key).await
+        }
+    };
+    health.key_info = health.step("Check API key", key_check).await;
+    health.record_latency();
+    if health.key_info.is_none() {
+        return health;
+    }
+
+    for model in settings.cloud_model.models() {
+        let found = health
+            .step(
+                format!("Find {}", model),
+                validator.validate_cloud_model(api_key, model, settings.catalog_ttl()),
+            )
+            .await;
+        if found.is_none() {
+            continue;
+        }
+        let generated = health
+            .step(
+                format!("Test {}", model),
+                validator.test_cloud_generation(api_key, model),
+            )
+            .await;
+        if generated.is_some() {
+            break;
+        }
+    }
+    health
+}
@@ -0,0 +1,106 @@
+//! Persisted history of submitted chat queries.
+//!
+//! Each profile keeps its own file in the user data directory, one JSON string
+//! per line with the newest query last, so multi-line queries survive intact.
+
+use crate::paths;
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const HISTORY_DIR: &str = "history";
+
+/// Queries kept per profile unless configured otherwise
+pub const DEFAULT_MAX_ENTRIES: usize = 1000;
+
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    path: Option<PathBuf>, // None keeps the history in memory only
+    entries: Vec<String>,
+    max_entries: usize,
+}
+
+impl History {
+    /// History file of `profile` inside the user data directory; `None` for
+    /// profiles that aren't plain names, such as `../x`, which would land
+    /// outside it
+    pub fn default_path(profile: &str) -> Option<PathBuf> {
+        if !paths::is_plain_file_name(profile) {
+            return None;
+        }
+        paths::data_dir().map(|dir| dir.join(HISTORY_DIR).join(format!("{}.jsonl", profile)))
+    }
+
+    pub fn load(profile: &str, max_entries: usize) -> Self {
+        match Self::default_path(profile) {
+            Some(path) => Self::load_from(&path, max_entries),
+            None => Self::in_memory(max_entries),
+        }
+    }
+
+    /// Read a history file; missing files and unreadable lines are skipped
+    pub fn load_from(path: &Path, max_entries: usize) -> Self {
+        let mut entries: Vec<String> = fs::read_to_string(path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        let excess = entries.len().saturating_sub(max_entries);
+        entries.drain(..excess);
+        Self {
+            path: Some(path.to_path_buf()),
+            entries,
+            max_entries,
+        }
+    }
+
+    pub fn in_memory(max_entries: usize) -> Self {
+        Self {
+            path: None,
+            entries: Vec::new(),
+            max_entries,
+        }
+    }
+
+    /// Oldest first
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Record a submitted query and write the file
+    ///
+    /// Repeating a query moves it to the end instead of storing it twice.
+    pub fn push(&mut self, query: &str) -> Result<()> {
+        if query.trim().is_empty() || self.max_entries == 0 {
+            return Ok(());
+        }
+        self.entries.retain(|entry| entry != query);
+        self.entries.push(query.to_string());
+        let excess = self.entries.len().saturating_sub(self.max_entries);
+        self.entries.drain(..excess);
+        self.save()
+    }
+
+    fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut contents = String::new();
+        for entry in &self.entries {
+            contents.push_str(&serde_json::to_string(entry)?);
+            contents.push('\n');
+        }
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Index of the newest entry before `before` that contains `needle`
+    pub fn search(&self, needle: &str, before: usize) -> Option<usize> {
+        self.entries[..before.min(self.entries.len())]
+            .iter()
+            .rposition(|entry| entry.contains(needle))
+    }
+}
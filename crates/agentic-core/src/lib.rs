@@ -6,20 +6,46 @@
 //!
 //! ## Modules
 //!
+//! - `cassette`: Record and replay of model traffic
+//! - `catalog`: On-disk cache of the OpenRouter model catalogue
+//! - `color_depth`: Terminal color support detection and downsampling
+//! - `crash`: Crash reports written when the TUI panics
+//! - `health`: Per-backend health checks for the status panel
+//! - `history`: Persisted history of submitted chat queries
+//! - `mock`: Built-in mock backend for demos and offline development
+//! - `model_filter`: Search, sorting and filtering for the model pickers
 //! - `models`: Data structures and validation logic for AI models
+//! - `notes`: Listing saved notes and reading them back
+//! - `ollama`: Pulling, deleting and inspecting Ollama models
+//! - `paths`: Per-user cache, data and notes directories
+//! - `repair`: Self-repair prompts for unparseable model output
+//! - `retry`: Exponential backoff for transient provider failures
 //! - `settings`: Application configuration management
 //! - `theme`: UI theming system
 
+pub mod cassette;
+pub mod catalog;
 pub mod cloud;
+pub mod color_depth;
+pub mod crash;
+pub mod health;
+pub mod history;
+pub mod mock;
+pub mod model_filter;
 pub mod models;
+pub mod notes;
+pub mod ollama;
 pub mod orchestrator;
+pub mod paths;
+pub mod repair;
+pub mod retry;
 pub mod settings;
 pub mod theme;
 
 #[cfg(test)]
 mod tests {
     use crate::models::ModelValidator;
-    use crate::settings::Settings;
+    use crate::settings::{RunMode, Settings, ValidationError};
     use crate::theme::ThemeVariant;
 
     #[test]
@@ -30,6 +56,7 @@ mod tests {
         assert_eq!(settings.local_model, "[SELECT]");
         assert_eq!(settings.api_key, "sk-or-v1-982...b52");
         assert_eq!(settings.cloud_model, "[SELECT]");
+        assert_eq!(settings.repair_rounds, 2);
     }
 
     #[test]
@@ -41,6 +68,792 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_local_only_mode() {
+        let settings = Settings {
+            run_mode: RunMode::LocalOnly,
+            local_model: "llama3.2:3b".to_string(),
+            ..Default::default()
+        };
+        // No API key or cloud model is required without the cloud
+        assert!(settings.is_valid().is_ok());
+        assert!(!settings.run_mode.uses_cloud());
+        assert_eq!(settings.run_mode.next(), RunMode::CloudOnly);
+
+        // Existing configs without a mode keep the hybrid behaviour
+        assert_eq!(Settings::default().run_mode, RunMode::Hybrid);
+    }
+
+    #[test]
+    fn test_cloud_only_mode() {
+        let settings = Settings {
+            run_mode: RunMode::CloudOnly,
+            cloud_model: "deepseek/deepseek-chat:free".into(),
+            api_key: "sk-or-v1-test".to_string(),
+            ..Default::default()
+        };
+        // The local model placeholder is fine when nothing runs locally
+        assert!(settings.is_valid().is_ok());
+        assert!(!settings.run_mode.uses_local());
+        assert!(settings.run_mode.uses_cloud());
+
+        let missing_key = Settings {
+            api_key: "sk-or-v1-982...b52".to_string(),
+            ..settings
+        };
+        assert_eq!(missing_key.is_valid(), Err(ValidationError::ApiKey));
+    }
+
+    #[tokio::test]
+    async fn test_provider_setting() {
+        use crate::models::LocalProvider;
+        use crate::settings::ProviderSetting;
+
+        let settings: Settings = toml::from_str(
+            &toml::to_string(&Settings {
+                endpoint: "127.0.0.1:8089".to_string(),
+                provider: ProviderSetting::LlamaCpp,
+                ..Default::default()
+            })
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(settings.provider, ProviderSetting::LlamaCpp);
+        assert_eq!(ProviderSetting::Auto.forced(), None);
+        assert_eq!(ProviderSetting::Auto.next(), ProviderSetting::Ollama);
+
+        // A pinned provider is used without probing the endpoint
+        settings.apply_provider_setting();
+        let detected = ModelValidator::new()
+            .detect_provider_type("127.0.0.1:8089")
+            .await;
+        assert_eq!(detected, LocalProvider::LlamaCpp);
+        assert!(detected.is_openai_compatible());
+    }
+
+    #[test]
+    fn test_cloud_model_chain() {
+        use crate::cloud::CloudError;
+
+        // A plain string still loads, and a list becomes an ordered chain
+        let single_toml = toml::to_string(&Settings::default()).unwrap();
+        assert!(single_toml.contains("cloud_model = \"[SELECT]\""));
+        let single: Settings = toml::from_str(&single_toml).unwrap();
+        assert_eq!(single.cloud_model, "[SELECT]");
+
+        let mut chain = crate::settings::ModelChain::from("[SELECT]");
+        chain.set_primary("deepseek/deepseek-chat:free".to_string());
+        chain.push_fallback("meta-llama/llama-3.1-8b-instruct:free".to_string());
+        chain.push_fallback("deepseek/deepseek-chat:free".to_string());
+        assert_eq!(chain.primary(), "deepseek/deepseek-chat:free");
+        assert_eq!(chain.fallbacks().len(), 1);
+        assert_eq!(
+            chain.to_string(),
+            "deepseek/deepseek-chat:free (+1 fallback)"
+        );
+
+        let settings = Settings {
+            cloud_model: chain.clone(),
+            ..Default::default()
+        };
+        let reloaded: Settings = toml::from_str(&toml::to_string(&settings).unwrap()).unwrap();
+        assert_eq!(reloaded.cloud_model, chain);
+
+        assert!(chain.clone().pop_fallback().is_some());
+        assert!(crate::settings::ModelChain::from("solo")
+            .pop_fallback()
+            .is_none());
+
+        // Removed models and rate limits move on to the next model; bad keys do not
+        assert!(CloudError::from_status(404, None, String::new()).should_fall_back());
+        assert!(CloudError::from_status(429, None, String::new()).should_fall_back());
+        assert!(!CloudError::from_status(401, None, String::new()).should_fall_back());
+    }
+
+    #[test]
+    fn test_model_picker_filtering() {
+        use crate::model_filter::{
+            filter_cloud_models, format_price_per_million, fuzzy_score, ModelFilter, ModelSort,
+        };
+        use crate::models::{ModelPricing, OpenRouterModel};
+
+        let model = |id: &str, name: &str, price: &str, context_length: u32| OpenRouterModel {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+            pricing: ModelPricing {
+                prompt: price.to_string(),
+                completion: price.to_string(),
+            },
+            context_length,
+        };
+        let models = vec![
+            model(
+                "anthropic/claude-3.5-sonnet",
+                "Claude 3.5 Sonnet",
+                "0.000003",
+                200_000,
+            ),
+            model(
+                "deepseek/deepseek-chat:free",
+                "DeepSeek V3 (free)",
+                "0",
+                64_000,
+            ),
+            model("openrouter/auto", "Auto Router", "-1", 2_000_000),
+        ];
+
+        // Subsequence matching, with word starts beating scattered letters
+        assert!(fuzzy_score("dsc", "deepseek chat").is_some());
+        assert!(fuzzy_score("xyz", "deepseek chat").is_none());
+        assert!(fuzzy_score("son", "sonnet").unwrap() > fuzzy_score("son", "season").unwrap());
+
+        let mut filter = ModelFilter {
+            query: "deepseek".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(filter_cloud_models(&models, &filter), vec![1]);
+
+        filter.query.clear();
+        filter.sort = ModelSort::Price;
+        assert_eq!(filter_cloud_models(&models, &filter), vec![1, 0, 2]);
+        filter.sort = ModelSort::Context;
+        assert_eq!(filter_cloud_models(&models, &filter), vec![2, 0, 1]);
+
+        filter.free_only = true;
+        assert_eq!(filter_cloud_models(&models, &filter), vec![1]);
+
+        assert_eq!(format_price_per_million("0.000003"), "$3.00/M");
+        assert_eq!(format_price_per_million("0"), "free");
+    }
+
+    #[test]
+    fn test_ollama_model_management() {
+        use crate::models::{format_relative_time, format_size};
+        use crate::ollama::{ModelInfo, PullProgress};
+
+        let show = serde_json::json!({
+            "parameters": "stop                           \"<|eot_id|>\"\ntemperature 0.6",
+            "template": "{{ .Prompt }}",
+            "details": {
+                "family": "llama",
+                "parameter_size": "3.2B",
+                "quantization_level": "Q4_K_M"
+            },
+            "model_info": { "llama.context_length": 131072 }
+        });
+        let info = ModelInfo::from_show_response(&show);
+        assert_eq!(info.quantization.as_deref(), Some("Q4_K_M"));
+        assert_eq!(info.context_length, Some(131072));
+        assert_eq!(
+            info.parameters.as_deref(),
+            Some("stop \"<|eot_id|>\", temperature 0.6")
+        );
+        assert_eq!(info.details().len(), 6);
+
+        let progress = PullProgress {
+            status: "pulling dde5aa3fc5ff".to_string(),
+            completed: Some(512),
+            total: Some(2048),
+        };
+        assert_eq!(progress.fraction(), Some(0.25));
+
+        assert_eq!(format_size(2_019_377_376), "1.9 GB");
+        assert_eq!(
+            format_relative_time("2024-01-02T03:04:05.123-07:00"),
+            "2024-01-02"
+        );
+        let three_hours_ago = (chrono::Utc::now() - chrono::Duration::hours(3)).to_rfc3339();
+        assert_eq!(format_relative_time(&three_hours_ago), "3h ago");
+    }
+
+    #[tokio::test]
+    async fn test_health_report() {
+        use crate::health::check_local;
+        use crate::models::KeyInfo;
+
+        // Nothing listens on the discard port, so only the first step runs
+        let settings = Settings {
+            endpoint: "127.0.0.1:9".to_string(),
+            local_model: "llama3.2:3b".to_string(),
+            ..Default::default()
+        };
+        let health = check_local(&settings).await;
+        assert!(!health.reachable());
+        assert!(!health.healthy());
+        assert_eq!(health.steps.len(), 1);
+        assert!(health.steps[0].error.is_some());
+        assert_eq!(
+            settings.validate_local_only().await,
+            Err(ValidationError::LocalEndpointUnreachable)
+        );
+
+        let key: KeyInfo = serde_json::from_value(serde_json::json!({
+            "label": "sk-or-v1-abc...def",
+            "usage": 1.5,
+            "limit": null,
+            "is_free_tier": true,
+            "rate_limit": { "requests": 20, "interval": "10s" }
+        }))
+        .unwrap();
+        assert_eq!(key.limit, None);
+        assert_eq!(key.rate_limit.unwrap().requests, 20);
+    }
+
+    #[tokio::test]
+    async fn test_mock_backend() {
+        use crate::catalog::load_picker_catalog;
+        use crate::cloud::call_cloud_model;
+        use crate::mock::MockBackend;
+        use crate::models::{reset_provider_cache, LocalProvider};
+        use crate::orchestrator::generate_proposals;
+        use crate::settings::{GenerationSettings, MockSettings};
+
+        // Pin the provider for this endpoint only, so other tests are unaffected
+        reset_provider_cache("mock-test", Some(LocalProvider::Mock));
+        let proposals = generate_proposals(
+            "why is the sky blue",
+            "mock-test",
+            "mock-orchestrator",
+            0,
+            &GenerationSettings::orchestrator(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(proposals.len(), 3);
+
+        let synthesis = call_cloud_model(
+            "",
+            &["mock/writer".to_string()],
+            &proposals[0],
+            0,
+            &GenerationSettings::synthesizer(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(synthesis.model, "mock/writer");
+        assert!(synthesis.note.header_tags.contains(&"mock".to_string()));
+
+        let flaky = MockBackend::new(MockSettings {
+            fail_every: 2,
+            ..Default::default()
+        });
+        assert!(flaky.reply("hello").await.is_ok());
+        assert_eq!(flaky.reply("hello").await.unwrap_err().status, 503);
+        reset_provider_cache("mock-test", None);
+
+        // A mock cloud chain lists the mock models without going online
+        let catalog = load_picker_catalog(
+            &ModelValidator::new(),
+            "",
+            &["mock/writer".to_string()],
+            std::time::Duration::ZERO,
+            true,
+        )
+        .await
+        .unwrap();
+        assert!(catalog.find("mock/writer").is_some());
+    }
+
+    #[test]
+    fn test_cassette_replay() {
+        use crate::cassette::{Backend, Cassette, Exchange, Player};
+
+        let exchange = |backend, model: &str, prompt: &str, response: &str| Exchange {
+            backend,
+            model: model.to_string(),
+            prompt: prompt.to_string(),
+            response: response.to_string(),
+        };
+        let cassette = Cassette {
+            version: 1,
+            exchanges: vec![
+                exchange(
+                    Backend::Local,
+                    "llama3.2:3b",
+                    "proposals",
+                    "{\"proposals\": [",
+                ),
+                exchange(Backend::Cloud, "deepseek/deepseek-chat:free", "a", "note a"),
+                exchange(Backend::Cloud, "deepseek/deepseek-chat:free", "b", "note b"),
+            ],
+        };
+
+        let path =
+            std::env::temp_dir().join(format!("ruixen-cassette-test-{}.json", std::process::id()));
+        cassette.save_to(&path).unwrap();
+        let loaded = Cassette::load_from(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(loaded.exchanges, cassette.exchanges);
+
+        // Exact matches first, then recording order for the same backend
+        let mut player = Player::new(loaded);
+        let cloud = |player: &mut Player, prompt| {
+            player.next_reply(Backend::Cloud, "deepseek/deepseek-chat:free", prompt)
+        };
+        assert_eq!(cloud(&mut player, "b").as_deref(), Some("note b"));
+        assert_eq!(cloud(&mut player, "changed").as_deref(), Some("note a"));
+        assert_eq!(cloud(&mut player, "a"), None);
+        assert_eq!(
+            player
+                .next_reply(Backend::Local, "other-model", "proposals")
+                .as_deref(),
+            Some("{\"proposals\": [")
+        );
+    }
+
+    #[test]
+    fn test_theme_files() {
+        use crate::color_depth::ColorDepth;
+        use crate::theme::{is_valid_theme_name, Element, Theme, ThemeFile};
+        use ratatui::style::{Color, Modifier};
+
+        // Built-ins keep their old config names; anything else is a user theme
+        let settings: Settings = toml::from_str(
+            &toml::to_string(&Settings {
+                theme: ThemeVariant::Nord,
+                ..Default::default()
+            })
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(settings.theme, ThemeVariant::Nord);
+        assert_eq!(
+            ThemeVariant::from("EverforestLight".to_string()),
+            ThemeVariant::EverforestLight
+        );
+        assert_eq!(
+            ThemeVariant::from("solarized".to_string()),
+            ThemeVariant::Custom("solarized".to_string())
+        );
+
+        let file: ThemeFile = toml::from_str(
+            r##"
+            base = "GruvboxDark"
+
+            [palette]
+            background = "#002b36"
+            accent = 136
+
+            [styles.title]
+            fg = "secondary"
+            italic = true
+            "##,
+        )
+        .unwrap();
+        let theme = Theme::from_file("solarized", &file).unwrap();
+        assert_eq!(theme.colors().background, Color::Rgb(0, 43, 54));
+        assert_eq!(theme.colors().accent, Color::Indexed(136));
+        // Unset entries come from the base theme
+        assert_eq!(theme.colors().warning, Color::Rgb(250, 189, 47));
+
+        let title = theme.ratatui_style(Element::Title);
+        assert_eq!(title.fg, Some(theme.colors().secondary));
+        assert!(title
+            .add_modifier
+            .contains(Modifier::ITALIC | Modifier::BOLD));
+
+        let bad: ThemeFile = toml::from_str("[palette]\naccent = \"not-a-color\"").unwrap();
+        assert!(Theme::from_file("bad", &bad).is_err());
+
+        // Names never leave the themes directory, and failures are kept for
+        // the picker instead of vanishing into the default theme
+        assert!(is_valid_theme_name("solarized"));
+        for name in ["../evil", "a/b", "a\\b", ".hidden", "C:evil", ""] {
+            assert!(!is_valid_theme_name(name), "{:?}", name);
+        }
+        assert!(Theme::load_custom("../evil").is_err());
+        let fallback = Theme::with_depth(
+            ThemeVariant::Custom("../evil".to_string()),
+            ColorDepth::TrueColor,
+        );
+        assert!(fallback.load_error().unwrap().contains("../evil"));
+        assert!(Theme::default().load_error().is_none());
+    }
+
+    #[test]
+    fn test_color_depth() {
+        use crate::color_depth::{parse_terminfo_colors, ColorDepth};
+        use crate::settings::ColorDepthSetting;
+        use crate::theme::{Element, Theme};
+        use ratatui::style::{Color, Modifier};
+
+        let detect =
+            |no_color, colorterm, term| ColorDepth::from_env(no_color, colorterm, term, None);
+        assert_eq!(detect(true, Some("truecolor"), None), ColorDepth::NoColor);
+        assert_eq!(
+            detect(false, Some("truecolor"), Some("screen")),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            detect(false, None, Some("tmux-256color")),
+            ColorDepth::Ansi256
+        );
+        assert_eq!(detect(false, None, Some("dumb")), ColorDepth::NoColor);
+        assert_eq!(
+            ColorDepth::from_env(false, None, Some("xterm-256color"), Some("Apple_Terminal")),
+            ColorDepth::Ansi256
+        );
+
+        // Header, names "xx|y", one boolean, then 14 numbers with colors last
+        let mut entry = vec![0x1a, 0x01, 5, 0, 1, 0, 14, 0, 0, 0, 0, 0];
+        entry.extend_from_slice(b"xx|y\0");
+        entry.push(1);
+        entry.extend(std::iter::repeat_n([0xff, 0xff], 13).flatten());
+        entry.extend_from_slice(&256i16.to_le_bytes());
+        assert_eq!(parse_terminfo_colors(&entry), Some(256));
+        assert_eq!(parse_terminfo_colors(b"not terminfo"), None);
+
+        // Everforest green and background land on their nearest palette entries
+        assert_eq!(
+            ColorDepth::Ansi256.adapt(Color::Rgb(167, 192, 128)),
+            Color::Indexed(144)
+        );
+        assert_eq!(
+            ColorDepth::Ansi256.adapt(Color::Rgb(45, 53, 59)),
+            Color::Indexed(236)
+        );
+        assert_eq!(
+            ColorDepth::Ansi16.adapt(Color::Rgb(230, 126, 128)),
+            Color::LightRed
+        );
+        assert_eq!(
+            ColorDepth::Ansi16.adapt(Color::Rgb(167, 192, 128)),
+            Color::Green
+        );
+        assert_eq!(
+            ColorDepth::Ansi16.adapt(Color::Indexed(196)),
+            Color::LightRed
+        );
+
+        let plain = Theme::with_depth(ThemeVariant::Nord, ColorDepth::NoColor);
+        assert_eq!(plain.colors().accent, Color::Reset);
+        assert!(plain
+            .ratatui_style(Element::Highlight)
+            .add_modifier
+            .contains(Modifier::REVERSED));
+
+        let forced: Settings = toml::from_str(
+            &toml::to_string(&Settings::default())
+                .unwrap()
+                .replace("color_depth = \"auto\"", "color_depth = \"256\""),
+        )
+        .unwrap();
+        assert_eq!(forced.color_depth, ColorDepthSetting::Ansi256);
+        assert_eq!(forced.color_depth.resolve(), ColorDepth::Ansi256);
+    }
+
+    #[test]
+    fn test_keymap_settings() {
+        use crate::settings::{KeymapPreset, Settings};
+
+        // Without a [keymap] table the default preset applies
+        let settings = Settings::default();
+        assert_eq!(settings.keymap.preset, KeymapPreset::Default);
+        assert!(settings.keymap.overrides.is_empty());
+
+        let config = toml::to_string(&settings).unwrap().replace(
+            "[keymap]\npreset = \"default\"\n",
+            "[keymap]\npreset = \"vim\"\n\n[keymap.note]\nsave = [\"ctrl+s\", \"w\"]\n",
+        );
+        let settings: Settings = toml::from_str(&config).unwrap();
+        assert_eq!(settings.keymap.preset, KeymapPreset::Vim);
+        assert_eq!(
+            settings.keymap.overrides["note"]["save"],
+            vec!["ctrl+s".to_string(), "w".to_string()]
+        );
+
+        // Overrides survive a save and reload
+        let saved = toml::to_string(&settings).unwrap();
+        let reloaded: Settings = toml::from_str(&saved).unwrap();
+        assert_eq!(reloaded.keymap, settings.keymap);
+    }
+
+    #[test]
+    fn test_mouse_setting() {
+        use crate::settings::Settings;
+
+        // Older configs without the key keep the mouse on
+        let config = toml::to_string(&Settings::default()).unwrap();
+        assert!(config.contains("mouse = true\n"));
+        let settings: Settings = toml::from_str(&config.replace("mouse = true\n", "")).unwrap();
+        assert!(settings.mouse);
+
+        let settings: Settings =
+            toml::from_str(&config.replace("mouse = true", "mouse = false")).unwrap();
+        assert!(!settings.mouse);
+    }
+
+    #[test]
+    fn test_query_history() {
+        use crate::history::History;
+
+        let path =
+            std::env::temp_dir().join(format!("ruixen-history-{}.jsonl", std::process::id()));
+        std::fs::remove_file(&path).ok();
+        let mut history = History::load_from(&path, 3);
+        history.push("what is rust").unwrap();
+        history.push("explain\nborrowing").unwrap();
+        history.push("what is rust").unwrap();
+        history.push("   ").unwrap();
+        // Repeats move to the end and blank queries are skipped
+        assert_eq!(history.entries(), ["explain\nborrowing", "what is rust"]);
+
+        history.push("tokio tasks").unwrap();
+        history.push("rust traits").unwrap();
+        let reloaded = History::load_from(&path, 3);
+        assert_eq!(
+            reloaded.entries(),
+            ["what is rust", "tokio tasks", "rust traits"]
+        );
+
+        // Reverse search walks back from the newest match
+        assert_eq!(reloaded.search("rust", 3), Some(2));
+        assert_eq!(reloaded.search("rust", 2), Some(0));
+        assert_eq!(reloaded.search("rust", 0), None);
+        assert_eq!(reloaded.search("python", 3), None);
+        std::fs::remove_file(&path).ok();
+
+        // Profiles name a file in the history folder and nothing else
+        for profile in ["../x", "a/b", "a\\b", ".hidden", ""] {
+            assert_eq!(History::default_path(profile), None, "{:?}", profile);
+        }
+    }
+
+    #[test]
+    fn test_crash_report() {
+        use crate::crash;
+
+        let dir = std::env::temp_dir().join(format!("ruixen-crashes-{}", std::process::id()));
+        let path = crash::write_report(
+            &dir,
+            "0.1.4",
+            "panicked at src/ui/app.rs:1:1:\nboom",
+            "0: main",
+        )
+        .unwrap();
+        let report = std::fs::read_to_string(&path).unwrap();
+        assert!(path.starts_with(&dir));
+        assert!(report.starts_with("Ruixen 0.1.4 crashed at "));
+        assert!(report.contains("boom"));
+        assert!(report.contains("Backtrace:\n0: main"));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_saved_notes() {
+        use crate::notes;
+        use std::time::{Duration, SystemTime};
+
+        let saved = "---\ndate: 2025-01-01\ntags: [rust, ownership]\n\nusage:\n  cloud_model: \"x\"\n---\n\n# rust • ownership\n\nBorrowing **rules**.\n";
+        let note = notes::parse(saved);
+        assert_eq!(note.header_tags, ["rust", "ownership"]);
+        assert_eq!(note.body_text, "Borrowing **rules**.");
+
+        // Without the heading the front matter still has the tags
+        let edited = notes::parse("---\ntags: [a, b]\n---\nJust text\n");
+        assert_eq!(edited.header_tags, ["a", "b"]);
+        assert_eq!(edited.body_text, "Just text");
+        assert!(notes::parse("No front matter").header_tags.is_empty());
+
+        let dir = std::env::temp_dir().join(format!("ruixen-notes-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("old.md"), saved).unwrap();
+        std::fs::write(dir.join("new.md"), "Untitled thoughts").unwrap();
+        std::fs::write(dir.join("ignored.txt"), "not a note").unwrap();
+        let old = SystemTime::now() - Duration::from_secs(60);
+        std::fs::File::options()
+            .write(true)
+            .open(dir.join("old.md"))
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        // Newest first, titled by the tag heading or the file name
+        let index = notes::index(&dir);
+        let titles: Vec<&str> = index.iter().map(|note| note.title.as_str()).collect();
+        assert_eq!(titles, ["new", "rust • ownership"]);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_model_catalog_cache() {
+        use crate::catalog::{estimate_cost_usd, ModelCatalog};
+        use crate::models::{ModelPricing, OpenRouterModel};
+        use std::time::Duration;
+
+        let mut catalog = ModelCatalog::new(vec![OpenRouterModel {
+            id: "anthropic/claude-3.5-sonnet".to_string(),
+            name: "Claude 3.5 Sonnet".to_string(),
+            description: String::new(),
+            pricing: ModelPricing {
+                prompt: "0.000003".to_string(),
+                completion: "0.000015".to_string(),
+            },
+            context_length: 200_000,
+        }]);
+
+        let path =
+            std::env::temp_dir().join(format!("ruixen-catalog-test-{}.json", std::process::id()));
+        catalog.save_to(&path).unwrap();
+        let reloaded = ModelCatalog::load_from(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert!(reloaded.is_fresh(Duration::from_secs(60)));
+        assert!(reloaded.find("anthropic/claude-3.5-sonnet").is_some());
+        assert!(reloaded.find("missing/model").is_none());
+
+        let pricing = reloaded.pricing("anthropic/claude-3.5-sonnet").unwrap();
+        let cost = estimate_cost_usd(pricing, 1000, 1000).unwrap();
+        assert!((cost - 0.018).abs() < 1e-9);
+
+        // An old download is stale, and a corrupt file is simply ignored
+        catalog.fetched_at -= 2 * 24 * 60 * 60;
+        assert!(!catalog.is_fresh(Duration::from_secs(24 * 60 * 60)));
+        assert!(
+            ModelCatalog::load_from(std::path::Path::new("/nonexistent/catalog.json")).is_none()
+        );
+    }
+
+    #[test]
+    fn test_repair_prompt() {
+        use crate::repair::build_repair_prompt;
+
+        let prompt = build_repair_prompt(
+            "{\"proposals\": [\"unterminated",
+            "EOF while parsing a string",
+            "{\"proposals\": []}",
+        );
+
+        // The model must see its own output, the parse error and the target format
+        assert!(prompt.contains("{\"proposals\": [\"unterminated"));
+        assert!(prompt.contains("EOF while parsing a string"));
+        assert!(prompt.contains("{\"proposals\": []}"));
+        assert!(!prompt.contains("{error}"));
+    }
+
+    #[tokio::test]
+    async fn test_parse_with_repair() {
+        use crate::repair::parse_with_repair;
+        use std::cell::RefCell;
+
+        let parse = |reply: &str| reply.parse::<u32>().map_err(|e| e.to_string());
+
+        // Each repair prompt carries the broken reply; the second fix parses
+        let prompts = RefCell::new(Vec::new());
+        let replies = RefCell::new(vec!["still broken", "42"].into_iter());
+        let request = |prompt: String| {
+            prompts.borrow_mut().push(prompt);
+            let reply = replies.borrow_mut().next().unwrap().to_string();
+            async move { Ok::<_, String>(reply) }
+        };
+        let parsed = parse_with_repair("test", "x".into(), 2, "1", parse, request).await;
+        assert_eq!(parsed, Ok(Ok(42)));
+        assert_eq!(prompts.borrow().len(), 2);
+        assert!(prompts.borrow()[1].contains("still broken"));
+
+        // Out of rounds: the last parse error comes back
+        let request = |_: String| async { Ok::<_, String>("nope".to_string()) };
+        let parsed = parse_with_repair("test", "x".into(), 1, "1", parse, request).await;
+        assert!(matches!(parsed, Ok(Err(_))));
+
+        // A failed request stops straight away
+        let request = |_: String| async { Err::<String, _>("offline".to_string()) };
+        let parsed = parse_with_repair("test", "x".into(), 3, "1", parse, request).await;
+        assert_eq!(parsed, Err::<Result<u32, String>, _>("offline".to_string()));
+    }
+
+    #[test]
+    fn test_cloud_error_classification() {
+        use crate::cloud::CloudError;
+        use crate::retry::{retry_after_from_str, RetryPolicy, Retryable};
+        use std::time::Duration;
+
+        let rate_limited =
+            CloudError::from_status(429, Some(Duration::from_secs(7)), String::new());
+        assert!(rate_limited.is_retryable());
+        assert_eq!(rate_limited.retry_after(), Some(Duration::from_secs(7)));
+
+        assert!(CloudError::from_status(502, None, "bad gateway".to_string()).is_retryable());
+        assert!(matches!(
+            CloudError::from_status(402, None, String::new()),
+            CloudError::InsufficientCredits
+        ));
+        assert!(matches!(
+            CloudError::from_status(
+                400,
+                None,
+                "This model's maximum context length is 8192 tokens".to_string()
+            ),
+            CloudError::ContextLengthExceeded { .. }
+        ));
+        assert!(!CloudError::from_status(401, None, String::new()).is_retryable());
+
+        // Exponential backoff, capped, with provider hints taking precedence
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay_for(1, None), Duration::from_secs(1));
+        assert_eq!(policy.delay_for(3, None), Duration::from_secs(4));
+        assert_eq!(policy.delay_for(10, None), policy.max_delay);
+        assert_eq!(
+            policy.delay_for(1, Some(Duration::from_secs(5))),
+            Duration::from_secs(5)
+        );
+
+        // Retry-After comes as delta-seconds or as an HTTP date
+        let now = chrono::DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert_eq!(
+            retry_after_from_str(" 120 ", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            retry_after_from_str("Wed, 21 Oct 2015 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            retry_after_from_str("Wed, 21 Oct 2015 07:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(retry_after_from_str("soon", now), None);
+    }
+
+    #[test]
+    fn test_generation_settings_editing() {
+        use crate::settings::GenerationSettings;
+
+        let mut params = GenerationSettings::orchestrator();
+        params
+            .apply_compact_string("timeout=60 temperature=- top_p=0.9 seed=42 num_ctx=8192")
+            .unwrap();
+
+        assert_eq!(params.timeout_secs, 60);
+        assert_eq!(params.temperature, None);
+        assert_eq!(params.top_p, Some(0.9));
+        assert_eq!(params.max_tokens, Some(2000)); // Untouched keys keep their value
+        assert_eq!(params.seed, Some(42));
+        assert_eq!(params.ollama_options["num_ctx"], serde_json::json!(8192));
+
+        // The compact form round-trips, and bad input leaves settings unchanged
+        let mut copy = GenerationSettings::synthesizer();
+        copy.apply_compact_string(&params.to_compact_string())
+            .unwrap();
+        assert_eq!(copy, params);
+        assert!(copy.apply_compact_string("timeout=soon").is_err());
+        assert!(copy.apply_compact_string("timeout=0").is_err());
+        assert_eq!(copy, params);
+
+        // Per-role tables survive a trip through config.toml
+        let settings = Settings {
+            orchestrator: params.clone(),
+            ..Settings::default()
+        };
+        let toml_string = toml::to_string_pretty(&settings).unwrap();
+        let loaded: Settings = toml::from_str(&toml_string).unwrap();
+        assert_eq!(loaded.orchestrator, params);
+        assert_eq!(loaded.synthesizer, GenerationSettings::synthesizer());
+    }
+
     #[tokio::test]
     async fn test_openrouter_integration() {
         let validator = ModelValidator::new();
@@ -163,10 +976,12 @@ mod tests {
                 println!("  Cloud: {}", cloud_models[0].name);
 
                 // Create settings with actual working models
-                let mut settings = Settings::default();
-                settings.local_model = local_models[0].name.clone();
-                settings.cloud_model = cloud_models[0].id.clone();
-                settings.api_key = api_key.to_string();
+                let settings = Settings {
+                    local_model: local_models[0].name.clone(),
+                    cloud_model: cloud_models[0].id.clone().into(),
+                    api_key: api_key.to_string(),
+                    ..Settings::default()
+                };
 
                 // Test local-only validation
                 match settings.validate_local_only().await {
@@ -0,0 +1,186 @@
+//! Built-in mock backend for demos and offline development.
+//!
+//! Selecting the `mock` provider serves the local endpoint from canned,
+//! deterministic responses, and any cloud model whose id starts with `mock/`
+//! is answered the same way. Optional latency and failure injection make the
+//! spinners, retries and fallbacks visible without a network.
+
+use crate::models::{KeyInfo, LocalModel, LocalProvider, LocalStatusError};
+use crate::models::{ModelPricing, OpenRouterModel};
+use crate::settings::MockSettings;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+/// Cloud model ids with this prefix never leave the process
+pub const MOCK_MODEL_PREFIX: &str = "mock/";
+
+const LOCAL_MODELS: &[&str] = &["mock-orchestrator", "mock-tiny"];
+const CLOUD_MODELS: &[(&str, &str)] = &[
+    ("mock/writer", "Mock Writer"),
+    ("mock/careful-writer", "Mock Careful Writer"),
+];
+
+static MOCK_BACKEND: OnceLock<Mutex<Option<Arc<MockBackend>>>> = OnceLock::new();
+
+fn global_backend() -> &'static Mutex<Option<Arc<MockBackend>>> {
+    MOCK_BACKEND.get_or_init(|| Mutex::new(None))
+}
+
+/// Enable the mock with the given settings, or disable it with `None`
+pub fn configure(settings: Option<MockSettings>) {
+    if let Ok(mut backend) = global_backend().lock() {
+        *backend = settings.map(|settings| Arc::new(MockBackend::new(settings)));
+    }
+}
+
+/// Whether the `mock` provider is selected
+pub fn enabled() -> bool {
+    global_backend()
+        .lock()
+        .map(|backend| backend.is_some())
+        .unwrap_or(false)
+}
+
+/// The configured mock, or one without latency or failures
+pub fn backend() -> Arc<MockBackend> {
+    global_backend()
+        .lock()
+        .ok()
+        .and_then(|backend| backend.clone())
+        .unwrap_or_else(|| Arc::new(MockBackend::new(MockSettings::default())))
+}
+
+pub fn is_mock_model(model: &str) -> bool {
+    model.starts_with(MOCK_MODEL_PREFIX)
+}
+
+pub fn local_models() -> Vec<LocalModel> {
+    LOCAL_MODELS
+        .iter()
+        .map(|name| LocalModel {
+            name: name.to_string(),
+            id: name.to_string(),
+            provider: LocalProvider::Mock,
+            size: "0 B".to_string(),
+            size_bytes: Some(0),
+            modified: "just now".to_string(),
+        })
+        .collect()
+}
+
+pub fn cloud_models() -> Vec<OpenRouterModel> {
+    CLOUD_MODELS
+        .iter()
+        .map(|(id, name)| OpenRouterModel {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: "Canned responses from the built-in mock backend".to_string(),
+            pricing: ModelPricing {
+                prompt: "0".to_string(),
+                completion: "0".to_string(),
+            },
+            context_length: 8192,
+        })
+        .collect()
+}
+
+pub fn key_info() -> KeyInfo {
+    KeyInfo {
+        label: Some("mock".to_string()),
+        usage: 0.0,
+        limit: None,
+        limit_remaining: None,
+        is_free_tier: true,
+        rate_limit: None,
+    }
+}
+
+/// Answers prompts with canned proposals and notes
+#[derive(Debug)]
+pub struct MockBackend {
+    settings: MockSettings,
+    requests: AtomicU64,
+}
+
+impl MockBackend {
+    pub fn new(settings: MockSettings) -> Self {
+        Self {
+            settings,
+            requests: AtomicU64::new(0),
+        }
+    }
+
+    /// Reply to a prompt after the configured latency
+    ///
+    /// Every `fail_every`-th request fails with a retryable 503.
+    pub async fn reply(&self, prompt: &str) -> Result<String, LocalStatusError> {
+        if self.settings.latency_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(self.settings.latency_ms)).await;
+        }
+
+        let request = self.requests.fetch_add(1, Ordering::Relaxed) + 1;
+        if self.settings.fail_every > 0 && request.is_multiple_of(self.settings.fail_every as u64) {
+            return Err(LocalStatusError {
+                status: 503,
+                text: format!("Mock failure injected on request {}", request),
+            });
+        }
+
+        Ok(canned_reply(prompt))
+    }
+}
+
+/// Pick a response shape from what the prompt asks for
+fn canned_reply(prompt: &str) -> String {
+    if prompt.contains("header_tags") {
+        let topic = prompt
+            .rsplit_once("USER PROMPT:")
+            .map(|(_, topic)| topic.trim())
+            .filter(|topic| !topic.is_empty())
+            .unwrap_or("your question");
+        mock_note(topic)
+    } else if prompt.contains("\"proposals\"") {
+        let query = prompt
+            .split_once("about this query: \"")
+            .and_then(|(_, rest)| rest.split_once('"'))
+            .map(|(query, _)| query)
+            .unwrap_or("this topic");
+        mock_proposals(query)
+    } else {
+        "Mock reply".to_string()
+    }
+}
+
+fn mock_proposals(query: &str) -> String {
+    serde_json::json!({
+        "proposals": [
+            format!("Most explanations of {} start from first principles - I wonder which assumption matters most?", query),
+            format!("{} looks different across fields - I wonder how a historian would frame it?", query),
+            format!("Everyday experience hints at {} - I'm wondering what a simple experiment would show?", query),
+        ]
+    })
+    .to_string()
+}
+
+fn mock_note(topic: &str) -> String {
+    // The longest words make stable, topic-specific tags
+    let mut words: Vec<String> = topic
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() > 3)
+        .map(str::to_lowercase)
+        .collect();
+    words.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    words.dedup();
+    let mut tags: Vec<String> = words.into_iter().take(3).collect();
+    tags.push("mock".to_string());
+
+    serde_json::json!({
+        "header_tags": tags,
+        "body_text": format!(
+            "This note was written by the mock backend for: {} It is deterministic, so the same proposal always yields the same note. Switch the provider back to a real backend for actual synthesis.",
+            topic
+        ),
+    })
+    .to_string()
+}
@@ -0,0 +1,165 @@
+//! Search, sorting and filtering for the model pickers.
+//!
+//! OpenRouter lists hundreds of models, so the picker narrows them down with a
+//! fuzzy query, a sort order and an optional "free only" toggle. The functions
+//! return indices into the original list so the caller keeps ownership.
+
+use crate::models::{LocalModel, OpenRouterModel};
+use std::cmp::Ordering;
+
+/// Sort orders offered by the model pickers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ModelSort {
+    #[default]
+    Name,
+    Price,
+    Context,
+    Size,
+}
+
+/// Sort orders that make sense for OpenRouter models
+pub const CLOUD_SORTS: &[ModelSort] = &[ModelSort::Name, ModelSort::Price, ModelSort::Context];
+
+/// Sort orders that make sense for local models
+pub const LOCAL_SORTS: &[ModelSort] = &[ModelSort::Name, ModelSort::Size];
+
+impl ModelSort {
+    /// Next sort order within `options`, wrapping around
+    pub fn cycle(self, options: &[ModelSort]) -> Self {
+        let position = options.iter().position(|&sort| sort == self);
+        match position {
+            Some(index) => options[(index + 1) % options.len()],
+            None => options.first().copied().unwrap_or_default(),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ModelSort::Name => "name",
+            ModelSort::Price => "price",
+            ModelSort::Context => "context",
+            ModelSort::Size => "size",
+        }
+    }
+}
+
+/// Current picker query and toggles
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelFilter {
+    pub query: String,
+    pub sort: ModelSort,
+    pub free_only: bool,
+}
+
+/// Score `candidate` against a fuzzy `query`, or `None` when it does not match
+///
+/// Every query character must appear in order. Consecutive characters and
+/// matches at word starts score higher, gaps score lower.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for wanted in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = position + candidate[position..].iter().position(|&c| c == wanted)?;
+        score += 1;
+        if previous_match.is_some_and(|previous| previous + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !candidate[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        score -= (found - position).min(3) as i64;
+        previous_match = Some(found);
+        position = found + 1;
+    }
+
+    Some(score)
+}
+
+/// Price in USD per token, or `None` when OpenRouter reports variable pricing
+pub fn price_per_token(price: &str) -> Option<f64> {
+    price.parse::<f64>().ok().filter(|value| *value >= 0.0)
+}
+
+/// Format a per-token price as dollars per million tokens
+pub fn format_price_per_million(price: &str) -> String {
+    match price_per_token(price) {
+        Some(0.0) => "free".to_string(),
+        Some(value) => format!("${:.2}/M", value * 1_000_000.0),
+        None => "variable".to_string(),
+    }
+}
+
+/// Format a context window compactly, e.g. `128k`
+pub fn format_context_length(tokens: u32) -> String {
+    if tokens >= 1_000_000 {
+        format!("{:.1}M", tokens as f64 / 1_000_000.0)
+    } else if tokens >= 1000 {
+        format!("{}k", tokens / 1000)
+    } else {
+        tokens.to_string()
+    }
+}
+
+/// Indices of the OpenRouter models matching `filter`, in display order
+pub fn filter_cloud_models(models: &[OpenRouterModel], filter: &ModelFilter) -> Vec<usize> {
+    let mut matches: Vec<(usize, i64)> = models
+        .iter()
+        .enumerate()
+        .filter(|(_, model)| !filter.free_only || model.is_free())
+        .filter_map(|(index, model)| {
+            let haystack = format!("{} {}", model.name, model.id);
+            fuzzy_score(&filter.query, &haystack).map(|score| (index, score))
+        })
+        .collect();
+
+    matches.sort_by(|(a, a_score), (b, b_score)| {
+        let (a, b) = (&models[*a], &models[*b]);
+        let by_key = match filter.sort {
+            ModelSort::Price => compare_prices(a, b),
+            ModelSort::Context => b.context_length.cmp(&a.context_length),
+            // Best matches first while searching, alphabetical otherwise
+            ModelSort::Name | ModelSort::Size => b_score.cmp(a_score),
+        };
+        by_key.then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+
+    matches.into_iter().map(|(index, _)| index).collect()
+}
+
+/// Indices of the local models matching `filter`, in display order
+pub fn filter_local_models(models: &[LocalModel], filter: &ModelFilter) -> Vec<usize> {
+    let mut matches: Vec<(usize, i64)> = models
+        .iter()
+        .enumerate()
+        .filter_map(|(index, model)| {
+            fuzzy_score(&filter.query, &model.name).map(|score| (index, score))
+        })
+        .collect();
+
+    matches.sort_by(|(a, a_score), (b, b_score)| {
+        let (a, b) = (&models[*a], &models[*b]);
+        let by_key = match filter.sort {
+            // Largest first; unknown sizes sink to the bottom
+            ModelSort::Size => b.size_bytes.cmp(&a.size_bytes),
+            _ => b_score.cmp(a_score),
+        };
+        by_key.then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+
+    matches.into_iter().map(|(index, _)| index).collect()
+}
+
+fn compare_prices(a: &OpenRouterModel, b: &OpenRouterModel) -> Ordering {
+    // Cheapest first; variable pricing sorts last
+    let total = |model: &OpenRouterModel| match (
+        price_per_token(&model.pricing.prompt),
+        price_per_token(&model.pricing.completion),
+    ) {
+        (Some(prompt), Some(completion)) => prompt + completion,
+        _ => f64::INFINITY,
+    };
+    total(a).total_cmp(&total(b))
+}
@@ -1,4 +1,12 @@
+use crate::cassette::{self, Backend};
+use crate::catalog::{format_age, load_catalog, DEFAULT_CATALOG_TTL_SECS};
+use crate::cloud::cloud_egress_enabled;
+use crate::mock;
+use crate::model_filter::price_per_token;
+use crate::retry::{retry_with_backoff, RetryPolicy, Retryable};
+use crate::settings::GenerationSettings;
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -13,6 +21,20 @@ fn get_global_provider_cache() -> &'static Arc<Mutex<HashMap<String, LocalProvid
     PROVIDER_CACHE.get_or_init(|| Arc::new(Mutex::new(HashMap::new())))
 }
 
+/// Forget the provider detected for `endpoint`, optionally pinning one instead
+///
+/// Called whenever the endpoint or provider setting changes and before each
+/// validation, so a server that was restarted as a different backend is
+/// detected again.
+pub fn reset_provider_cache(endpoint: &str, forced: Option<LocalProvider>) {
+    if let Ok(mut cache_lock) = get_global_provider_cache().lock() {
+        match forced {
+            Some(provider) => cache_lock.insert(endpoint.to_string(), provider),
+            None => cache_lock.remove(endpoint),
+        };
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AtomicNote {
     pub header_tags: Vec<String>,
@@ -23,6 +45,7 @@ pub struct AtomicNote {
 pub struct OllamaModel {
     pub name: String,
     pub size: String,
+    pub size_bytes: u64,
     pub modified: String,
 }
 
@@ -32,6 +55,8 @@ pub struct LocalModel {
     pub id: String,
     pub provider: LocalProvider,
     pub size: String,
+    /// Size on disk when the provider reports it, used for sorting
+    pub size_bytes: Option<u64>,
     pub modified: String,
 }
 
@@ -40,6 +65,16 @@ pub enum LocalProvider {
     Ollama,
     LMStudio,
     OpenAI,
+    LlamaCpp,
+    /// Canned responses from the built-in mock backend
+    Mock,
+}
+
+impl LocalProvider {
+    /// Whether the provider speaks the OpenAI `/v1` API rather than Ollama's
+    pub fn is_openai_compatible(&self) -> bool {
+        !matches!(self, LocalProvider::Ollama | LocalProvider::Mock)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,12 +86,74 @@ pub struct OpenRouterModel {
     pub context_length: u32,
 }
 
+impl OpenRouterModel {
+    /// Whether both prompt and completion tokens cost nothing
+    pub fn is_free(&self) -> bool {
+        price_per_token(&self.pricing.prompt) == Some(0.0)
+            && price_per_token(&self.pricing.completion) == Some(0.0)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelPricing {
     pub prompt: String,
     pub completion: String,
 }
 
+/// OpenRouter's view of the API key: spend, limits and rate limit
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct KeyInfo {
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Credits used so far, in USD
+    #[serde(default)]
+    pub usage: f64,
+    /// Credit limit in USD; `None` means unlimited
+    #[serde(default)]
+    pub limit: Option<f64>,
+    #[serde(default)]
+    pub limit_remaining: Option<f64>,
+    #[serde(default)]
+    pub is_free_tier: bool,
+    #[serde(default)]
+    pub rate_limit: Option<RateLimit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RateLimit {
+    pub requests: i64,
+    pub interval: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct KeyInfoResponse {
+    data: KeyInfo,
+}
+
+/// The endpoint answered, but does not offer the requested model
+#[derive(Debug, thiserror::Error)]
+#[error("Model '{model}' not found {location}")]
+pub struct ModelNotFoundError {
+    pub model: String,
+    location: &'static str,
+}
+
+impl ModelNotFoundError {
+    fn local(model: &str) -> Self {
+        Self {
+            model: model.to_string(),
+            location: "on local endpoint",
+        }
+    }
+
+    fn cloud(model: &str) -> Self {
+        Self {
+            model: model.to_string(),
+            location: "in OpenRouter",
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct OllamaListResponse {
     models: Vec<OllamaModelRaw>,
@@ -69,6 +166,16 @@ struct OllamaModelRaw {
     modified_at: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct OllamaRunningResponse {
+    models: Vec<OllamaRunningModel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaRunningModel {
+    name: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct OpenRouterListResponse {
     data: Vec<OpenRouterModelRaw>,
@@ -127,7 +234,10 @@ impl ModelValidator {
         }
 
         // Perform actual detection
-        let detected_provider = self.perform_provider_detection(endpoint).await;
+        let Some(detected_provider) = self.perform_provider_detection(endpoint).await else {
+            // Nothing answered; don't cache a guess so the next call probes again
+            return LocalProvider::Ollama;
+        };
 
         // Cache the result globally
         if let Ok(mut cache_lock) = cache.lock() {
@@ -137,57 +247,54 @@ impl ModelValidator {
         detected_provider
     }
 
-    async fn perform_provider_detection(&self, endpoint: &str) -> LocalProvider {
-        // Try OpenAI/LM Studio API first for port 1234
-        if endpoint.to_lowercase().contains("1234")
-            && self.test_openai_endpoint(endpoint).await.is_ok()
-        {
-            return LocalProvider::LMStudio;
+    /// Identify the server from the endpoints only that backend exposes
+    async fn perform_provider_detection(&self, endpoint: &str) -> Option<LocalProvider> {
+        // Ollama reports {"version": "..."} here
+        if let Some(body) = self.probe_json(endpoint, "/api/version").await {
+            if body.get("version").is_some() {
+                return Some(LocalProvider::Ollama);
+            }
         }
 
-        // Try Ollama API
-        if self.test_ollama_endpoint(endpoint).await.is_ok() {
-            return LocalProvider::Ollama;
+        // llama.cpp's server publishes its slot and generation settings
+        if let Some(body) = self.probe_json(endpoint, "/props").await {
+            if body.get("default_generation_settings").is_some()
+                || body.get("total_slots").is_some()
+            {
+                return Some(LocalProvider::LlamaCpp);
+            }
         }
 
-        // Try generic OpenAI API
-        if self.test_openai_endpoint(endpoint).await.is_ok() {
-            return LocalProvider::OpenAI;
+        // LM Studio's native REST API lives next to its OpenAI one
+        if let Some(body) = self.probe_json(endpoint, "/api/v0/models").await {
+            if body.get("data").is_some() {
+                return Some(LocalProvider::LMStudio);
+            }
         }
 
-        // Default to Ollama if all detection fails
-        LocalProvider::Ollama
-    }
-
-    async fn test_ollama_endpoint(&self, endpoint: &str) -> Result<()> {
-        let url = if endpoint.starts_with("http") {
-            format!("{}/api/tags", endpoint)
-        } else {
-            format!("http://{}/api/tags", endpoint)
-        };
-
-        let response = self.client.get(&url).send().await?;
-        if response.status().is_success() {
-            Ok(())
-        } else {
-            Err(anyhow::anyhow!("Ollama endpoint not accessible"))
+        // Any other server with an OpenAI-compatible model list
+        if let Some(body) = self.probe_json(endpoint, "/v1/models").await {
+            if body.get("data").is_some() {
+                return Some(LocalProvider::OpenAI);
+            }
         }
+
+        None
     }
 
-    async fn test_openai_endpoint(&self, endpoint: &str) -> Result<()> {
-        let normalized_endpoint = endpoint.to_lowercase();
-        let url = if normalized_endpoint.starts_with("http") {
-            format!("{}/v1/models", normalized_endpoint)
+    /// GET `path` on the endpoint, returning the JSON body on success
+    async fn probe_json(&self, endpoint: &str, path: &str) -> Option<Value> {
+        let url = if endpoint.starts_with("http") {
+            format!("{}{}", endpoint, path)
         } else {
-            format!("http://{}/v1/models", normalized_endpoint)
+            format!("http://{}{}", endpoint, path)
         };
 
-        let response = self.client.get(&url).send().await?;
-        if response.status().is_success() {
-            Ok(())
-        } else {
-            Err(anyhow::anyhow!("OpenAI endpoint not accessible"))
+        let response = self.client.get(&url).send().await.ok()?;
+        if !response.status().is_success() {
+            return None;
         }
+        response.json().await.ok()
     }
 
     pub async fn fetch_local_models(&self, endpoint: &str) -> Result<Vec<LocalModel>> {
@@ -203,14 +310,16 @@ impl ModelValidator {
                         id: model.name,
                         provider: LocalProvider::Ollama,
                         size: model.size,
+                        size_bytes: Some(model.size_bytes),
                         modified: model.modified,
                     })
                     .collect();
                 Ok(local_models)
             }
-            LocalProvider::LMStudio | LocalProvider::OpenAI => {
-                self.fetch_openai_models(endpoint).await
+            LocalProvider::LMStudio | LocalProvider::OpenAI | LocalProvider::LlamaCpp => {
+                self.fetch_openai_models(endpoint, provider).await
             }
+            LocalProvider::Mock => Ok(mock::local_models()),
         }
     }
 
@@ -234,7 +343,8 @@ impl ModelValidator {
             .into_iter()
             .map(|raw| OllamaModel {
                 name: raw.name,
-                size: format_size(raw.size),
+                size: format_size(raw.size.max(0) as u64),
+                size_bytes: raw.size.max(0) as u64,
                 modified: format_relative_time(&raw.modified_at),
             })
             .collect();
@@ -243,6 +353,7 @@ impl ModelValidator {
     }
 
     pub async fn fetch_openrouter_models(&self, api_key: &str) -> Result<Vec<OpenRouterModel>> {
+        ensure_cloud_egress()?;
         let url = "https://openrouter.ai/api/v1/models";
 
         let response = self
@@ -292,7 +403,11 @@ impl ModelValidator {
         Ok(models)
     }
 
-    pub async fn fetch_openai_models(&self, endpoint: &str) -> Result<Vec<LocalModel>> {
+    pub async fn fetch_openai_models(
+        &self,
+        endpoint: &str,
+        provider: LocalProvider,
+    ) -> Result<Vec<LocalModel>> {
         let url = if endpoint.starts_with("http") {
             format!("{}/v1/models", endpoint)
         } else {
@@ -307,12 +422,6 @@ impl ModelValidator {
 
         let openai_response: OpenAIListResponse = response.json().await?;
 
-        let provider = if endpoint.contains("1234") {
-            LocalProvider::LMStudio
-        } else {
-            LocalProvider::OpenAI
-        };
-
         let models = openai_response
             .data
             .into_iter()
@@ -321,7 +430,12 @@ impl ModelValidator {
                 id: raw.id,
                 provider: provider.clone(),
                 size: "Unknown".to_string(),
-                modified: "recently".to_string(),
+                size_bytes: None,
+                modified: raw
+                    .created
+                    .and_then(|secs| DateTime::from_timestamp(secs as i64, 0))
+                    .map(|time| format_relative_to(time, Utc::now()))
+                    .unwrap_or_else(|| "unknown".to_string()),
             })
             .collect();
 
@@ -332,6 +446,13 @@ impl ModelValidator {
         let provider = self.detect_provider_type(endpoint).await;
 
         match provider {
+            LocalProvider::Mock => {
+                if mock::local_models().iter().any(|m| m.name == model) {
+                    Ok(())
+                } else {
+                    Err(ModelNotFoundError::local(model).into())
+                }
+            }
             LocalProvider::Ollama => {
                 let url = if endpoint.starts_with("http") {
                     format!("{}/api/tags", endpoint)
@@ -356,10 +477,7 @@ impl ModelValidator {
                     if model_exists {
                         Ok(())
                     } else {
-                        Err(anyhow::anyhow!(
-                            "Model '{}' not found on local endpoint",
-                            model
-                        ))
+                        Err(ModelNotFoundError::local(model).into())
                     }
                 } else {
                     Err(anyhow::anyhow!(
@@ -367,7 +485,7 @@ impl ModelValidator {
                     ))
                 }
             }
-            LocalProvider::LMStudio | LocalProvider::OpenAI => {
+            LocalProvider::LMStudio | LocalProvider::OpenAI | LocalProvider::LlamaCpp => {
                 let url = if endpoint.starts_with("http") {
                     format!("{}/v1/models", endpoint)
                 } else {
@@ -391,10 +509,7 @@ impl ModelValidator {
                     if model_exists {
                         Ok(())
                     } else {
-                        Err(anyhow::anyhow!(
-                            "Model '{}' not found on local endpoint",
-                            model
-                        ))
+                        Err(ModelNotFoundError::local(model).into())
                     }
                 } else {
                     Err(anyhow::anyhow!(
@@ -405,8 +520,27 @@ impl ModelValidator {
         }
     }
 
-    pub async fn validate_cloud_endpoint(&self, api_key: &str, model: &str) -> Result<()> {
-        let url = "https://openrouter.ai/api/v1/models";
+    /// Names of the models Ollama currently holds in memory (`/api/ps`)
+    pub async fn fetch_loaded_models(&self, endpoint: &str) -> Result<Vec<String>> {
+        let url = if endpoint.starts_with("http") {
+            format!("{}/api/ps", endpoint)
+        } else {
+            format!("http://{}/api/ps", endpoint)
+        };
+
+        let response = self.client.get(&url).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Ollama endpoint not accessible"));
+        }
+
+        let running: OllamaRunningResponse = response.json().await?;
+        Ok(running.models.into_iter().map(|m| m.name).collect())
+    }
+
+    /// Credits and limits for an OpenRouter API key
+    pub async fn fetch_openrouter_key_info(&self, api_key: &str) -> Result<KeyInfo> {
+        ensure_cloud_egress()?;
+        let url = "https://openrouter.ai/api/v1/key";
 
         let response = self
             .client
@@ -417,31 +551,72 @@ impl ModelValidator {
 
         if !response.status().is_success() {
             return Err(anyhow::anyhow!(
-                "Cloud API key invalid or endpoint not accessible"
+                "OpenRouter rejected the API key. Status: {}",
+                response.status().as_u16()
             ));
         }
 
-        let models: Value = response.json().await?;
+        let key_response: KeyInfoResponse = response.json().await?;
+        Ok(key_response.data)
+    }
 
-        if let Some(models_array) = models.get("data").and_then(|m| m.as_array()) {
-            let model_exists = models_array.iter().any(|m| {
-                m.get("id")
-                    .and_then(|id| id.as_str())
-                    .map(|id| id == model)
-                    .unwrap_or(false)
-            });
+    pub async fn validate_cloud_endpoint(&self, api_key: &str, model: &str) -> Result<()> {
+        self.validate_cloud_model(
+            api_key,
+            model,
+            Duration::from_secs(DEFAULT_CATALOG_TTL_SECS),
+        )
+        .await
+    }
 
-            if model_exists {
+    /// Check a model id against the cached catalogue instead of downloading it every time
+    pub async fn validate_cloud_model(
+        &self,
+        api_key: &str,
+        model: &str,
+        ttl: Duration,
+    ) -> Result<()> {
+        ensure_cloud_egress()?;
+        if mock::is_mock_model(model) {
+            return if mock::cloud_models().iter().any(|m| m.id == model) {
                 Ok(())
             } else {
-                Err(anyhow::anyhow!("Model '{}' not found in OpenRouter", model))
+                Err(ModelNotFoundError::cloud(model).into())
+            };
+        }
+
+        let catalog = load_catalog(self, api_key, ttl, false).await?;
+        if catalog.find(model).is_some() {
+            return Ok(());
+        }
+
+        // The model may be newer than our cached copy
+        if catalog.age() > Duration::from_secs(60) {
+            let refreshed = load_catalog(self, api_key, ttl, true).await?;
+            if refreshed.find(model).is_some() {
+                return Ok(());
             }
-        } else {
-            Err(anyhow::anyhow!("Invalid response format from OpenRouter"))
         }
+
+        Err(ModelNotFoundError::cloud(model).into())
     }
 
     pub async fn test_local_generation(&self, endpoint: &str, model: &str) -> Result<()> {
+        let provider = self.detect_provider_type(endpoint).await;
+        if provider == LocalProvider::Mock {
+            return Ok(());
+        }
+        if provider.is_openai_compatible() {
+            let params = GenerationSettings {
+                timeout_secs: 30,
+                max_tokens: Some(1),
+                ..GenerationSettings::synthesizer()
+            };
+            return call_openai_model(endpoint, model, "Hello", &params)
+                .await
+                .map(|_| ());
+        }
+
         let url = if endpoint.starts_with("http") {
             format!("{}/api/generate", endpoint)
         } else {
@@ -469,6 +644,10 @@ impl ModelValidator {
     }
 
     pub async fn test_cloud_generation(&self, api_key: &str, model: &str) -> Result<()> {
+        ensure_cloud_egress()?;
+        if mock::is_mock_model(model) {
+            return Ok(());
+        }
         let url = "https://openrouter.ai/api/v1/chat/completions";
 
         let payload = serde_json::json!({
@@ -501,6 +680,8 @@ struct LocalGenerationRequest<'a> {
     model: &'a str,
     prompt: &'a str,
     stream: bool,
+    #[serde(skip_serializing_if = "serde_json::Map::is_empty")]
+    options: serde_json::Map<String, Value>,
 }
 
 #[derive(Deserialize)]
@@ -508,28 +689,82 @@ struct LocalGenerationResponse {
     response: String,
 }
 
+/// A local model endpoint answered with a non-success status
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to get response from local model. Status: {status}. Error: {text}")]
+pub struct LocalStatusError {
+    pub status: u16,
+    pub text: String,
+}
+
+impl Retryable for anyhow::Error {
+    fn is_retryable(&self) -> bool {
+        if let Some(e) = self.downcast_ref::<LocalStatusError>() {
+            return e.status == 429 || ((500..600).contains(&e.status) && e.status != 501);
+        }
+        if let Some(e) = self.downcast_ref::<reqwest::Error>() {
+            return e.is_retryable();
+        }
+        false
+    }
+}
+
 pub async fn call_local_model(
     endpoint: &str,
     model: &str,
     prompt: &str,
+    params: &GenerationSettings,
 ) -> Result<String, anyhow::Error> {
+    // A replayed reply needs no endpoint, not even for provider detection
+    if let Some(reply) = cassette::replay(Backend::Local, model, prompt) {
+        return reply;
+    }
+
     let validator = ModelValidator::new();
     let provider = validator.detect_provider_type(endpoint).await;
 
-    match provider {
-        LocalProvider::Ollama => call_ollama_model(endpoint, model, prompt).await,
-        LocalProvider::LMStudio | LocalProvider::OpenAI => {
-            call_openai_model(endpoint, model, prompt).await
+    let reply = retry_with_backoff(&RetryPolicy::default(), "local", || async {
+        match provider {
+            LocalProvider::Ollama => call_ollama_model(endpoint, model, prompt, params).await,
+            LocalProvider::LMStudio | LocalProvider::OpenAI | LocalProvider::LlamaCpp => {
+                call_openai_model(endpoint, model, prompt, params).await
+            }
+            LocalProvider::Mock => Ok(mock::backend().reply(prompt).await?),
         }
+    })
+    .await?;
+    cassette::record(Backend::Local, model, prompt, &reply);
+    Ok(reply)
+}
+
+fn build_ollama_options(params: &GenerationSettings) -> serde_json::Map<String, Value> {
+    let mut options = serde_json::Map::new();
+    if let Some(temperature) = params.temperature {
+        options.insert("temperature".to_string(), temperature.into());
     }
+    if let Some(top_p) = params.top_p {
+        options.insert("top_p".to_string(), top_p.into());
+    }
+    if let Some(max_tokens) = params.max_tokens {
+        options.insert("num_predict".to_string(), max_tokens.into());
+    }
+    if let Some(seed) = params.seed {
+        options.insert("seed".to_string(), seed.into());
+    }
+    // Explicit Ollama options win over the generic parameters
+    for (key, value) in &params.ollama_options {
+        options.insert(key.clone(), value.clone());
+    }
+    options
 }
 
 pub async fn call_ollama_model(
     endpoint: &str,
     model: &str,
     prompt: &str,
+    params: &GenerationSettings,
 ) -> Result<String, anyhow::Error> {
-    let client = Client::new();
+    let client = Client::builder().timeout(params.timeout()).build()?;
     let url = if endpoint.starts_with("http") {
         format!("{}/api/generate", endpoint)
     } else {
@@ -540,6 +775,7 @@ pub async fn call_ollama_model(
         model,
         prompt,
         stream: false,
+        options: build_ollama_options(params),
     };
 
     let response = client.post(&url).json(&payload).send().await?;
@@ -548,10 +784,9 @@ pub async fn call_ollama_model(
         let gen_response: LocalGenerationResponse = response.json().await?;
         Ok(gen_response.response)
     } else {
-        Err(anyhow::anyhow!(
-            "Failed to get response from local model. Status: {}",
-            response.status()
-        ))
+        let status = response.status().as_u16();
+        let text = response.text().await.unwrap_or_default();
+        Err(LocalStatusError { status, text }.into())
     }
 }
 
@@ -559,8 +794,14 @@ pub async fn call_ollama_model(
 struct OpenAIGenerationRequest<'a> {
     model: &'a str,
     messages: Vec<serde_json::Value>,
-    max_tokens: u32,
-    temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
 }
 
 #[derive(Deserialize)]
@@ -582,8 +823,9 @@ pub async fn call_openai_model(
     endpoint: &str,
     model: &str,
     prompt: &str,
+    params: &GenerationSettings,
 ) -> Result<String, anyhow::Error> {
-    let client = Client::new();
+    let client = Client::builder().timeout(params.timeout()).build()?;
     let url = if endpoint.starts_with("http") {
         format!("{}/v1/chat/completions", endpoint)
     } else {
@@ -596,8 +838,10 @@ pub async fn call_openai_model(
             "role": "user",
             "content": prompt
         })],
-        max_tokens: 2000,
-        temperature: 0.7,
+        max_tokens: params.max_tokens,
+        temperature: params.temperature,
+        top_p: params.top_p,
+        seed: params.seed,
     };
 
     let response = client.post(&url).json(&payload).send().await?;
@@ -610,15 +854,21 @@ pub async fn call_openai_model(
             Err(anyhow::anyhow!("No response choices from OpenAI model"))
         }
     } else {
-        let status = response.status();
-        let error_text = response
+        let status = response.status().as_u16();
+        let text = response
             .text()
             .await
             .unwrap_or_else(|_| "Unknown error".to_string());
+        Err(LocalStatusError { status, text }.into())
+    }
+}
+
+fn ensure_cloud_egress() -> Result<()> {
+    if cloud_egress_enabled() {
+        Ok(())
+    } else {
         Err(anyhow::anyhow!(
-            "Failed to get response from OpenAI model. Status: {}. Error: {}",
-            status,
-            error_text
+            "Cloud requests are disabled in local-only mode"
         ))
     }
 }
@@ -629,7 +879,8 @@ impl Default for ModelValidator {
     }
 }
 
-fn format_size(bytes: i64) -> String {
+/// Human-readable size such as `4.7 GB`
+pub fn format_size(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
     let mut size = bytes as f64;
     let mut unit_index = 0;
@@ -640,14 +891,27 @@ fn format_size(bytes: i64) -> String {
     }
 
     if unit_index == 0 {
-        format!("{} {}", size as i64, UNITS[unit_index])
+        format!("{} {}", bytes, UNITS[unit_index])
     } else {
         format!("{:.1} {}", size, UNITS[unit_index])
     }
 }
 
-fn format_relative_time(_iso_time: &str) -> String {
-    // For now, just return a simple format
-    // Parse ISO time and return relative time
-    "recently".to_string()
+/// Relative time for an RFC 3339 timestamp, e.g. `3d ago`
+///
+/// Older than a month shows the date instead; unparseable input is returned as is.
+pub fn format_relative_time(iso_time: &str) -> String {
+    match DateTime::parse_from_rfc3339(iso_time) {
+        Ok(time) => format_relative_to(time.with_timezone(&Utc), Utc::now()),
+        Err(_) => iso_time.to_string(),
+    }
+}
+
+fn format_relative_to(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let age = (now - time).to_std().unwrap_or_default();
+    if age > Duration::from_secs(30 * 24 * 60 * 60) {
+        time.format("%Y-%m-%d").to_string()
+    } else {
+        format_age(age)
+    }
 }
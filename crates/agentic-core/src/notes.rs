@@ -0,0 +1,112 @@
+//! Saved notes: listing the notes folder and reading notes back.
+//!
+//! A saved note is Markdown with YAML front matter, a `# tag • tag` heading
+//! and the body. Reading one back also copes with notes edited by hand.
+
+use crate::models::AtomicNote;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone)]
+pub struct SavedNote {
+    pub path: PathBuf,
+    pub title: String, // The tag heading, or the file name without one
+    pub modified: SystemTime,
+}
+
+/// Markdown files in `dir`, newest first; a missing folder has none
+pub fn index(dir: &Path) -> Vec<SavedNote> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut notes: Vec<SavedNote> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            let tags = parse(&fs::read_to_string(&path).ok()?).header_tags;
+            let title = if tags.is_empty() {
+                path.file_stem()?.to_string_lossy().into_owned()
+            } else {
+                tags.join(" • ")
+            };
+            Some(SavedNote {
+                path,
+                title,
+                modified,
+            })
+        })
+        .collect();
+    notes.sort_by_key(|note| std::cmp::Reverse(note.modified));
+    notes
+}
+
+/// Read a note back; tags come from the heading, or the front matter without one
+pub fn parse(contents: &str) -> AtomicNote {
+    let (front_matter, rest) = split_front_matter(contents);
+    let rest = rest.trim_start();
+    let (header_tags, body) = match rest.strip_prefix("# ") {
+        Some(heading) => {
+            let (heading, body) = heading.split_once('\n').unwrap_or((heading, ""));
+            (split_tags(heading, '•'), body)
+        }
+        None => {
+            let tags = front_matter
+                .lines()
+                .find_map(|line| line.strip_prefix("tags:"))
+                .map(|tags| split_tags(tags.trim().trim_matches(['[', ']']), ','))
+                .unwrap_or_default();
+            (tags, rest)
+        }
+    };
+    AtomicNote {
+        header_tags,
+        body_text: body.trim().to_string(),
+    }
+}
+
+/// Front matter between `---` lines at the very top, and everything after it
+fn split_front_matter(contents: &str) -> (&str, &str) {
+    let Some(rest) = contents
+        .strip_prefix("---\n")
+        .or_else(|| contents.strip_prefix("---\r\n"))
+    else {
+        return ("", contents);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return (&rest[..offset], &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    ("", contents)
+}
+
+fn split_tags(text: &str, separator: char) -> Vec<String> {
+    text.split(separator)
+        .map(|tag| tag.trim().trim_matches('"'))
+        .filter(|tag| !tag.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// `$VISUAL` or `$EDITOR` split into program and arguments, so values such
+/// as `code --wait` work; `vi` (Notepad on Windows) when neither is set
+pub fn editor_command() -> Vec<String> {
+    let fallback = if cfg!(target_os = "windows") {
+        "notepad"
+    } else {
+        "vi"
+    };
+    ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| fallback.to_string())
+        .split_whitespace()
+        .map(String::from)
+        .collect()
+}
@@ -0,0 +1,218 @@
+//! Ollama model management: pulling, deleting and inspecting models.
+//!
+//! These calls use Ollama's native API, so they only work when the local
+//! endpoint is an Ollama server.
+
+use anyhow::Result;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::Value;
+use std::time::Duration;
+
+/// One progress update from a streamed `/api/pull`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullProgress {
+    pub status: String,
+    pub completed: Option<u64>,
+    pub total: Option<u64>,
+}
+
+impl PullProgress {
+    /// Share of the current layer downloaded, when Ollama reports sizes
+    pub fn fraction(&self) -> Option<f64> {
+        match (self.completed, self.total) {
+            (Some(completed), Some(total)) if total > 0 => {
+                Some((completed as f64 / total as f64).min(1.0))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PullEvent {
+    #[serde(default)]
+    status: String,
+    completed: Option<u64>,
+    total: Option<u64>,
+    error: Option<String>,
+}
+
+/// What `/api/show` reports about a model
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelInfo {
+    pub family: Option<String>,
+    pub parameter_size: Option<String>,
+    pub quantization: Option<String>,
+    pub context_length: Option<u64>,
+    pub parameters: Option<String>,
+    pub template: Option<String>,
+}
+
+impl ModelInfo {
+    pub fn from_show_response(body: &Value) -> Self {
+        let details = body.get("details");
+        let detail = |key: &str| {
+            details
+                .and_then(|d| d.get(key))
+                .and_then(Value::as_str)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        // Keyed by architecture, e.g. `llama.context_length`
+        let context_length = body
+            .get("model_info")
+            .and_then(Value::as_object)
+            .and_then(|info| {
+                info.iter()
+                    .find(|(key, _)| key.ends_with(".context_length"))
+                    .and_then(|(_, value)| value.as_u64())
+            });
+        let text = |key: &str| {
+            body.get(key)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        Self {
+            family: detail("family"),
+            parameter_size: detail("parameter_size"),
+            quantization: detail("quantization_level"),
+            context_length,
+            // One `key value` per line in the Modelfile; collapse for display
+            parameters: text("parameters").map(|params| {
+                params
+                    .lines()
+                    .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }),
+            template: text("template"),
+        }
+    }
+
+    /// (label, value) rows for the model picker's details pane
+    pub fn details(&self) -> Vec<(String, String)> {
+        let rows = [
+            ("Family", self.family.clone()),
+            ("Parameters", self.parameter_size.clone()),
+            ("Quantization", self.quantization.clone()),
+            (
+                "Context",
+                self.context_length
+                    .map(|tokens| format!("{} tokens", tokens)),
+            ),
+            ("Options", self.parameters.clone()),
+            ("Template", self.template.clone()),
+        ];
+        rows.into_iter()
+            .filter_map(|(label, value)| value.map(|value| (label.to_string(), value)))
+            .collect()
+    }
+}
+
+/// Download a model, reporting each streamed progress update
+pub async fn pull_model<F>(endpoint: &str, model: &str, mut on_progress: F) -> Result<()>
+where
+    F: FnMut(PullProgress),
+{
+    // Pulls take minutes, so only the connection is bounded
+    let client = Client::builder()
+        .connect_timeout(Duration::from_secs(5))
+        .build()?;
+    let payload = serde_json::json!({ "model": model, "stream": true });
+    let mut response = client
+        .post(api_url(endpoint, "/api/pull"))
+        .json(&payload)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status().as_u16();
+        let text = response.text().await.unwrap_or_default();
+        return Err(anyhow::anyhow!(
+            "Failed to pull '{}'. Status: {}. Error: {}",
+            model,
+            status,
+            text
+        ));
+    }
+
+    // Newline-delimited JSON; a chunk may end mid-line
+    let mut buffer = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        buffer.extend_from_slice(&chunk);
+        while let Some(newline) = buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=newline).collect();
+            handle_pull_line(&line, &mut on_progress)?;
+        }
+    }
+    handle_pull_line(&buffer, &mut on_progress)
+}
+
+fn handle_pull_line<F: FnMut(PullProgress)>(line: &[u8], on_progress: &mut F) -> Result<()> {
+    if line.iter().all(u8::is_ascii_whitespace) {
+        return Ok(());
+    }
+    let event: PullEvent = serde_json::from_slice(line)?;
+    if let Some(error) = event.error {
+        return Err(anyhow::anyhow!("Ollama pull failed: {}", error));
+    }
+    on_progress(PullProgress {
+        status: event.status,
+        completed: event.completed,
+        total: event.total,
+    });
+    Ok(())
+}
+
+pub async fn delete_model(endpoint: &str, model: &str) -> Result<()> {
+    let client = Client::builder().timeout(Duration::from_secs(30)).build()?;
+    let payload = serde_json::json!({ "model": model });
+    let response = client
+        .delete(api_url(endpoint, "/api/delete"))
+        .json(&payload)
+        .send()
+        .await?;
+
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Failed to delete '{}'. Status: {}",
+            model,
+            response.status().as_u16()
+        ))
+    }
+}
+
+pub async fn show_model(endpoint: &str, model: &str) -> Result<ModelInfo> {
+    let client = Client::builder().timeout(Duration::from_secs(10)).build()?;
+    let payload = serde_json::json!({ "model": model });
+    let response = client
+        .post(api_url(endpoint, "/api/show"))
+        .json(&payload)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!(
+            "Failed to inspect '{}'. Status: {}",
+            model,
+            response.status().as_u16()
+        ));
+    }
+
+    let body: Value = response.json().await?;
+    Ok(ModelInfo::from_show_response(&body))
+}
+
+fn api_url(endpoint: &str, path: &str) -> String {
+    if endpoint.starts_with("http") {
+        format!("{}{}", endpoint, path)
+    } else {
+        format!("http://{}{}", endpoint, path)
+    }
+}
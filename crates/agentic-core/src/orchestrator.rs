@@ -1,5 +1,9 @@
+use crate::cloud::call_cloud_completion;
 use crate::models::call_local_model;
+use crate::repair::parse_with_repair;
+use crate::settings::GenerationSettings;
 use serde::Deserialize;
+use std::future::Future;
 
 const ORCHESTRATOR_PROMPT: &str = r#"You are Ruixen, an inquisitive AI partner. 
 
@@ -28,6 +32,14 @@ You MUST generate EXACTLY 3 proposals about this query: "{query}"
 }
 "#;
 
+const PROPOSALS_FORMAT: &str = r#"{
+  "proposals": [
+    "Brief context statement - I wonder about this specific aspect?",
+    "Another context statement - I'm wondering if this could be true?",
+    "Third context statement - I wonder about this different angle?"
+  ]
+}"#;
+
 #[derive(Deserialize, Debug)]
 struct ProposalObject {
     context: String,
@@ -50,30 +62,96 @@ pub async fn generate_proposals(
     query: &str,
     endpoint: &str,
     model: &str,
+    repair_rounds: u32,
+    params: &GenerationSettings,
+) -> Result<Vec<String>, anyhow::Error> {
+    run_orchestrator(query, endpoint, model, repair_rounds, |prompt| async move {
+        request_proposals(endpoint, model, &prompt, params).await
+    })
+    .await
+}
+
+/// Generate proposals with the cloud model chain (cloud-only mode)
+pub async fn generate_cloud_proposals(
+    query: &str,
+    api_key: &str,
+    models: &[String],
+    repair_rounds: u32,
+    params: &GenerationSettings,
 ) -> Result<Vec<String>, anyhow::Error> {
+    let model_label = models.join(", ");
+    let model_label = model_label.as_str();
+    run_orchestrator(
+        query,
+        "openrouter.ai",
+        model_label,
+        repair_rounds,
+        |prompt| async move {
+            call_cloud_completion(api_key, models, &prompt, params)
+                .await
+                .map_err(|e| {
+                    let error_msg = format!(
+                        "Cloud model API call failed for models '{}': {}",
+                        model_label, e
+                    );
+                    std::fs::write("/tmp/debug_error.txt", &error_msg).ok();
+                    anyhow::anyhow!(error_msg)
+                })
+        },
+    )
+    .await
+}
+
+async fn run_orchestrator<F, Fut>(
+    query: &str,
+    endpoint: &str,
+    model: &str,
+    repair_rounds: u32,
+    mut request: F,
+) -> Result<Vec<String>, anyhow::Error>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<String, anyhow::Error>>,
+{
     let prompt = ORCHESTRATOR_PROMPT.replace("{query}", query);
 
     // Debug: Write the prompt to a file so we can see what's being sent
     std::fs::write("/tmp/debug_prompt.txt", &prompt).ok();
 
-    let response_str = match call_local_model(endpoint, model, &prompt).await {
-        Ok(response) => response,
-        Err(e) => {
+    let response_str = request(prompt).await?;
+
+    // Debug: Write the response to a file so we can see what came back
+    std::fs::write("/tmp/debug_response.txt", &response_str).ok();
+
+    // Try multiple JSON extraction strategies
+    parse_with_repair(
+        "orchestrator",
+        response_str,
+        repair_rounds,
+        PROPOSALS_FORMAT,
+        |reply| parse_proposals_with_fallbacks(reply, endpoint, model),
+        request,
+    )
+    .await?
+}
+
+async fn request_proposals(
+    endpoint: &str,
+    model: &str,
+    prompt: &str,
+    params: &GenerationSettings,
+) -> Result<String, anyhow::Error> {
+    call_local_model(endpoint, model, prompt, params)
+        .await
+        .map_err(|e| {
             // Enhanced error with more context
             let error_msg = format!(
                 "Local model API call failed for endpoint '{}' with model '{}': {}",
                 endpoint, model, e
             );
             std::fs::write("/tmp/debug_error.txt", &error_msg).ok();
-            return Err(anyhow::anyhow!(error_msg));
-        }
-    };
-
-    // Debug: Write the response to a file so we can see what came back
-    std::fs::write("/tmp/debug_response.txt", &response_str).ok();
-
-    // Try multiple JSON extraction strategies
-    parse_proposals_with_fallbacks(&response_str, endpoint, model)
+            anyhow::anyhow!(error_msg)
+        })
 }
 
 fn parse_proposals_with_fallbacks(
@@ -85,20 +163,24 @@ fn parse_proposals_with_fallbacks(
     let clean_response = extract_json_from_markdown(response_str);
 
     // Strategy 2: Try to find and parse the JSON object
+    let mut json_error = "no JSON object found".to_string();
     if let Some(json_start) = clean_response.find("{") {
         let json_str = &clean_response[json_start..];
-        if let Ok(response) = serde_json::from_str::<ProposalsResponse>(json_str) {
-            let proposals = response
-                .proposals
-                .into_iter()
-                .map(|item| match item {
-                    ProposalItem::StringFormat(s) => s,
-                    ProposalItem::ObjectFormat(obj) => {
-                        format!("{} - {}", obj.context, obj.question)
-                    }
-                })
-                .collect();
-            return Ok(proposals);
+        match serde_json::from_str::<ProposalsResponse>(json_str) {
+            Ok(response) => {
+                let proposals = response
+                    .proposals
+                    .into_iter()
+                    .map(|item| match item {
+                        ProposalItem::StringFormat(s) => s,
+                        ProposalItem::ObjectFormat(obj) => {
+                            format!("{} - {}", obj.context, obj.question)
+                        }
+                    })
+                    .collect();
+                return Ok(proposals);
+            }
+            Err(e) => json_error = e.to_string(),
         }
     }
 
@@ -120,9 +202,10 @@ fn parse_proposals_with_fallbacks(
     std::fs::write("/tmp/debug_parse_failure.txt", &debug_info).ok();
 
     Err(anyhow::anyhow!(
-        "Local model '{}' at '{}' did not return parseable proposals. Response was: '{}'",
+        "Model '{}' at '{}' did not return parseable proposals ({}). Response was: '{}'",
         model,
         endpoint,
+        json_error,
         response_str.chars().take(200).collect::<String>()
     ))
 }
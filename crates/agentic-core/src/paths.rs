@@ -0,0 +1,71 @@
+//! Per-user directories for cached and persistent application data.
+//!
+//! Follows the platform conventions (XDG on Linux, `~/Library` on macOS,
+//! `%LOCALAPPDATA%`/`%APPDATA%` on Windows) with a `ruixen` subdirectory.
+
+use std::env;
+use std::path::PathBuf;
+
+const APP_DIR: &str = "ruixen";
+
+/// Directory for data that can be re-downloaded, such as the model catalogue
+pub fn cache_dir() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        env_path("LOCALAPPDATA")
+    } else if cfg!(target_os = "macos") {
+        home_dir().map(|home| home.join("Library").join("Caches"))
+    } else {
+        env_path("XDG_CACHE_HOME").or_else(|| home_dir().map(|home| home.join(".cache")))
+    };
+    base.map(|dir| dir.join(APP_DIR))
+}
+
+/// Directory for user-authored configuration, such as custom themes
+pub fn config_dir() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        env_path("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        home_dir().map(|home| home.join("Library").join("Application Support"))
+    } else {
+        env_path("XDG_CONFIG_HOME").or_else(|| home_dir().map(|home| home.join(".config")))
+    };
+    base.map(|dir| dir.join(APP_DIR))
+}
+
+/// Directory for data the user would miss, such as history or crash reports
+pub fn data_dir() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        env_path("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        home_dir().map(|home| home.join("Library").join("Application Support"))
+    } else {
+        env_path("XDG_DATA_HOME")
+            .or_else(|| home_dir().map(|home| home.join(".local").join("share")))
+    };
+    base.map(|dir| dir.join(APP_DIR))
+}
+
+/// Folder synthesized notes are saved to, `~/Documents/ruixen`, where note
+/// apps such as Obsidian can pick them up
+pub fn notes_dir() -> PathBuf {
+    home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("Documents")
+        .join(APP_DIR)
+}
+
+/// Whether `name` stays directly inside a directory once joined to it, e.g.
+/// a theme or history profile name from the config
+pub fn is_plain_file_name(name: &str) -> bool {
+    !name.is_empty() && !name.starts_with('.') && !name.contains(['/', '\\', ':'])
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env_path("HOME").or_else(|| env_path("USERPROFILE"))
+}
+
+fn env_path(name: &str) -> Option<PathBuf> {
+    env::var_os(name)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
@@ -0,0 +1,79 @@
+//! Self-repair prompts for model output that fails to parse.
+//!
+//! When every parsing strategy gives up, the model is shown its own broken
+//! output together with the parse error and asked for corrected JSON.
+
+use std::fmt::Display;
+use std::future::Future;
+
+const REPAIR_PROMPT: &str = r#"Your previous answer could not be parsed as valid JSON.
+
+**PARSE ERROR:**
+{error}
+
+**YOUR PREVIOUS ANSWER:**
+{broken}
+
+**CRITICAL INSTRUCTION:**
+Return ONLY the corrected JSON object. Keep the original content, fix the syntax, and match this exact format:
+{format}
+
+Do not add explanations, markdown code fences or any text outside the JSON object.
+"#;
+
+/// Default number of repair rounds attempted after a parse failure
+pub const DEFAULT_REPAIR_ROUNDS: u32 = 2;
+
+/// Build the prompt asking a model to fix its own unparseable output
+pub fn build_repair_prompt(broken: &str, error: &str, expected_format: &str) -> String {
+    REPAIR_PROMPT
+        .replace("{error}", error)
+        .replace("{broken}", broken)
+        .replace("{format}", expected_format)
+}
+
+/// Parse `reply`, and while that fails hand the model its broken output and
+/// the parse error, at most `rounds` times
+///
+/// A failed request ends the loop with its error; otherwise the inner result
+/// is the parsed value or the last parse error.
+pub(crate) async fn parse_with_repair<T, PE, E, P, F, Fut>(
+    source: &str,
+    mut reply: String,
+    rounds: u32,
+    expected_format: &str,
+    mut parse: P,
+    mut request: F,
+) -> Result<Result<T, PE>, E>
+where
+    PE: Display,
+    P: FnMut(&str) -> Result<T, PE>,
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<String, E>>,
+{
+    let mut round = 0;
+    loop {
+        match parse(&reply) {
+            Err(e) if round < rounds => {
+                round += 1;
+                let error = e.to_string();
+                log_repair_attempt(source, round, &error, &reply);
+                reply = request(build_repair_prompt(&reply, &error, expected_format)).await?;
+            }
+            parsed => return Ok(parsed),
+        }
+    }
+}
+
+/// Record a repair attempt alongside the other debug dumps
+fn log_repair_attempt(source: &str, round: u32, error: &str, response: &str) {
+    let debug_info = format!(
+        "Repair attempt {} for {}\nParse Error: {}\nResponse: {}",
+        round, source, error, response
+    );
+    std::fs::write(
+        format!("/tmp/debug_repair_{}_{}.txt", source, round),
+        debug_info,
+    )
+    .ok();
+}
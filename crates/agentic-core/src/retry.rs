@@ -0,0 +1,107 @@
+//! Retry with exponential backoff for transient provider failures.
+//!
+//! Rate limits, upstream outages and timeouts are usually gone a few seconds
+//! later, so both the local and cloud call paths retry them before surfacing
+//! an error to the user.
+
+use std::future::Future;
+use std::time::Duration;
+
+/// Errors that know whether another attempt could succeed
+pub trait Retryable {
+    /// Whether the failed request is worth sending again
+    fn is_retryable(&self) -> bool;
+
+    /// Delay requested by the provider, e.g. from a `Retry-After` header
+    fn retry_after(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// How often and how patiently a request is retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before the given retry (1-based), honouring a provider hint
+    pub fn delay_for(&self, retry: u32, hint: Option<Duration>) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)));
+        hint.unwrap_or(exponential).min(self.max_delay)
+    }
+}
+
+/// Run `operation` until it succeeds, fails permanently or runs out of retries
+pub async fn retry_with_backoff<T, E, F, Fut>(
+    policy: &RetryPolicy,
+    label: &str,
+    mut operation: F,
+) -> Result<T, E>
+where
+    E: Retryable + std::fmt::Display,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut retry = 0;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(e) if e.is_retryable() && retry < policy.max_retries => {
+                retry += 1;
+                let delay = policy.delay_for(retry, e.retry_after());
+                let debug_info = format!(
+                    "Retry {} of {} for {} in {:?}\nError: {}",
+                    retry, policy.max_retries, label, delay, e
+                );
+                std::fs::write(format!("/tmp/debug_retry_{}.txt", label), debug_info).ok();
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+impl Retryable for reqwest::Error {
+    fn is_retryable(&self) -> bool {
+        self.is_timeout() || self.is_connect()
+    }
+}
+
+/// Parse a `Retry-After` header, given either in seconds or as an HTTP date
+pub fn parse_retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| retry_after_from_str(value, chrono::Utc::now()))
+}
+
+/// Delay a `Retry-After` value asks for, measured from `now`; dates already
+/// past mean retry right away
+pub fn retry_after_from_str(value: &str, now: chrono::DateTime<chrono::Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    // IMF-fixdate, e.g. `Wed, 21 Oct 2015 07:28:00 GMT`, is valid RFC 2822
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&chrono::Utc) - now)
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
@@ -1,11 +1,22 @@
-use crate::models::ModelValidator;
+use crate::cassette;
+use crate::catalog::DEFAULT_CATALOG_TTL_SECS;
+use crate::cloud;
+use crate::color_depth::ColorDepth;
+use crate::history;
+use crate::mock;
+use crate::models::{self, LocalProvider, ModelNotFoundError, ModelValidator};
+use crate::repair::DEFAULT_REPAIR_ROUNDS;
 use crate::theme::ThemeVariant;
 use figment::{
     providers::{Format, Toml},
     Figment,
 };
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::fs;
+use std::time::Duration;
 
 const LOCAL_MODEL_PLACEHOLDER: &str = "[SELECT]";
 const CLOUD_MODEL_PLACEHOLDER: &str = "[SELECT]";
@@ -22,23 +33,516 @@ pub enum ValidationError {
     CloudModelNotFound,
 }
 
+/// Which backends take part in a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum RunMode {
+    /// Local model orchestrates, cloud model synthesizes (default)
+    #[default]
+    Hybrid,
+    /// The local model runs both roles and nothing is sent to the cloud
+    LocalOnly,
+    /// The cloud model runs both roles; no local model is needed
+    CloudOnly,
+}
+
+impl RunMode {
+    /// Cycle to the next mode (used by the settings modal)
+    pub fn next(self) -> Self {
+        match self {
+            RunMode::Hybrid => RunMode::LocalOnly,
+            RunMode::LocalOnly => RunMode::CloudOnly,
+            RunMode::CloudOnly => RunMode::Hybrid,
+        }
+    }
+
+    /// Whether this mode needs a local model at all
+    pub fn uses_local(self) -> bool {
+        self != RunMode::CloudOnly
+    }
+
+    /// Whether this mode talks to the cloud provider at all
+    pub fn uses_cloud(self) -> bool {
+        self != RunMode::LocalOnly
+    }
+}
+
+/// Which kind of server the local endpoint is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum ProviderSetting {
+    /// Probe the endpoint's identity routes (default)
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    #[serde(rename = "ollama")]
+    Ollama,
+    #[serde(rename = "lmstudio")]
+    LmStudio,
+    #[serde(rename = "openai-compatible")]
+    OpenAiCompatible,
+    #[serde(rename = "llama.cpp")]
+    LlamaCpp,
+    /// Canned responses for demos and offline development
+    #[serde(rename = "mock")]
+    Mock,
+}
+
+impl ProviderSetting {
+    /// Cycle to the next provider (used by the settings modal)
+    pub fn next(self) -> Self {
+        match self {
+            ProviderSetting::Auto => ProviderSetting::Ollama,
+            ProviderSetting::Ollama => ProviderSetting::LmStudio,
+            ProviderSetting::LmStudio => ProviderSetting::OpenAiCompatible,
+            ProviderSetting::OpenAiCompatible => ProviderSetting::LlamaCpp,
+            ProviderSetting::LlamaCpp => ProviderSetting::Mock,
+            ProviderSetting::Mock => ProviderSetting::Auto,
+        }
+    }
+
+    /// The provider to use without probing, or `None` to auto-detect
+    pub fn forced(self) -> Option<LocalProvider> {
+        match self {
+            ProviderSetting::Auto => None,
+            ProviderSetting::Ollama => Some(LocalProvider::Ollama),
+            ProviderSetting::LmStudio => Some(LocalProvider::LMStudio),
+            ProviderSetting::OpenAiCompatible => Some(LocalProvider::OpenAI),
+            ProviderSetting::LlamaCpp => Some(LocalProvider::LlamaCpp),
+            ProviderSetting::Mock => Some(LocalProvider::Mock),
+        }
+    }
+}
+
+/// Ordered cloud models; the first is preferred, the rest are fallbacks
+///
+/// Accepts either a single model id or a list in `config.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "ModelChainRepr", into = "ModelChainRepr")]
+pub struct ModelChain(Vec<String>);
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum ModelChainRepr {
+    Single(String),
+    Chain(Vec<String>),
+}
+
+impl From<ModelChainRepr> for ModelChain {
+    fn from(repr: ModelChainRepr) -> Self {
+        match repr {
+            ModelChainRepr::Single(model) => ModelChain::from(model),
+            ModelChainRepr::Chain(models) if models.is_empty() => {
+                ModelChain::from(CLOUD_MODEL_PLACEHOLDER)
+            }
+            ModelChainRepr::Chain(models) => ModelChain(models),
+        }
+    }
+}
+
+impl From<ModelChain> for ModelChainRepr {
+    fn from(chain: ModelChain) -> Self {
+        // Keep single-model configs in their original, simpler form
+        match <[String; 1]>::try_from(chain.0) {
+            Ok([model]) => ModelChainRepr::Single(model),
+            Err(models) => ModelChainRepr::Chain(models),
+        }
+    }
+}
+
+impl From<String> for ModelChain {
+    fn from(model: String) -> Self {
+        ModelChain(vec![model])
+    }
+}
+
+impl From<&str> for ModelChain {
+    fn from(model: &str) -> Self {
+        ModelChain(vec![model.to_string()])
+    }
+}
+
+impl ModelChain {
+    /// The preferred model, tried first
+    pub fn primary(&self) -> &str {
+        &self.0[0]
+    }
+
+    /// All models in the order they are tried
+    pub fn models(&self) -> &[String] {
+        &self.0
+    }
+
+    /// Models tried after the primary one fails
+    pub fn fallbacks(&self) -> &[String] {
+        &self.0[1..]
+    }
+
+    /// Replace the preferred model, keeping the fallbacks
+    pub fn set_primary(&mut self, model: String) {
+        self.0.retain(|m| m != &model);
+        if self.0.is_empty() || self.0[0] == CLOUD_MODEL_PLACEHOLDER {
+            self.0.insert(0, model);
+            self.0.retain(|m| m != CLOUD_MODEL_PLACEHOLDER);
+        } else {
+            self.0[0] = model;
+        }
+    }
+
+    /// Append a fallback model unless it is already in the chain
+    pub fn push_fallback(&mut self, model: String) {
+        if self.0[0] == CLOUD_MODEL_PLACEHOLDER {
+            self.0[0] = model;
+        } else if !self.0.contains(&model) {
+            self.0.push(model);
+        }
+    }
+
+    /// Remove the last fallback, never the primary model
+    pub fn pop_fallback(&mut self) -> Option<String> {
+        if self.0.len() > 1 {
+            self.0.pop()
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for ModelChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.fallbacks().len() {
+            0 => write!(f, "{}", self.primary()),
+            1 => write!(f, "{} (+1 fallback)", self.primary()),
+            n => write!(f, "{} (+{} fallbacks)", self.primary(), n),
+        }
+    }
+}
+
+impl PartialEq<&str> for ModelChain {
+    fn eq(&self, other: &&str) -> bool {
+        self.0.len() == 1 && self.0[0] == *other
+    }
+}
+
+impl PartialEq<str> for ModelChain {
+    fn eq(&self, other: &str) -> bool {
+        self.0.len() == 1 && self.0[0] == other
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Settings {
     pub theme: ThemeVariant,
+    /// Colors the terminal can show; `auto` detects them
+    #[serde(default)]
+    pub color_depth: ColorDepthSetting,
+    #[serde(default)]
+    pub run_mode: RunMode,
     pub endpoint: String,
+    #[serde(default)]
+    pub provider: ProviderSetting,
     pub local_model: String,
     pub api_key: String,
-    pub cloud_model: String,
+    pub cloud_model: ModelChain,
+    /// How many times a model is asked to fix output that failed to parse
+    #[serde(default = "default_repair_rounds")]
+    pub repair_rounds: u32,
+    /// Generation parameters for the role that turns a query into proposals
+    #[serde(default = "GenerationSettings::orchestrator")]
+    pub orchestrator: GenerationSettings,
+    /// Generation parameters for the role that turns a proposal into a note
+    #[serde(default = "GenerationSettings::synthesizer")]
+    pub synthesizer: GenerationSettings,
+    /// How long the cached OpenRouter model catalogue stays fresh
+    #[serde(default = "default_catalog_ttl_secs")]
+    pub catalog_ttl_secs: u64,
+    /// Latency and failure injection for the `mock` provider
+    #[serde(default)]
+    pub mock: MockSettings,
+    /// Record model replies to, or replay them from, a cassette file
+    #[serde(default)]
+    pub cassette: CassetteSettings,
+    /// Key binding preset and per-mode overrides
+    #[serde(default)]
+    pub keymap: KeymapSettings,
+    /// Where submitted queries are remembered for recall
+    #[serde(default)]
+    pub history: HistorySettings,
+    /// Mouse wheel and clicks; off leaves text selection to the terminal
+    #[serde(default = "default_mouse")]
+    pub mouse: bool,
+}
+
+fn default_repair_rounds() -> u32 {
+    DEFAULT_REPAIR_ROUNDS
+}
+
+fn default_mouse() -> bool {
+    true
+}
+
+fn default_catalog_ttl_secs() -> u64 {
+    DEFAULT_CATALOG_TTL_SECS
+}
+
+/// Behaviour of the built-in mock backend
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MockSettings {
+    /// Delay added to every mock response
+    #[serde(default)]
+    pub latency_ms: u64,
+    /// Fail every Nth request with a retryable 503; 0 never fails
+    #[serde(default)]
+    pub fail_every: u32,
+}
+
+/// Detected or forced terminal color depth
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ColorDepthSetting {
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    #[serde(rename = "truecolor")]
+    TrueColor,
+    #[serde(rename = "256")]
+    Ansi256,
+    #[serde(rename = "16")]
+    Ansi16,
+    #[serde(rename = "none")]
+    NoColor,
+}
+
+impl ColorDepthSetting {
+    /// The forced depth, or the one detected from the environment
+    pub fn resolve(self) -> ColorDepth {
+        match self {
+            ColorDepthSetting::Auto => ColorDepth::detect(),
+            ColorDepthSetting::TrueColor => ColorDepth::TrueColor,
+            ColorDepthSetting::Ansi256 => ColorDepth::Ansi256,
+            ColorDepthSetting::Ansi16 => ColorDepth::Ansi16,
+            ColorDepthSetting::NoColor => ColorDepth::NoColor,
+        }
+    }
+}
+
+/// Built-in set of key bindings that overrides apply on top of
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeymapPreset {
+    #[default]
+    Default,
+    Vim,
+}
+
+/// Key bindings as configured, e.g. `[keymap.note] save = ["ctrl+s"]`
+///
+/// Overrides map a mode to action names and the keys bound to each. The TUI
+/// validates the names and key syntax when it builds the keymap.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct KeymapSettings {
+    #[serde(default)]
+    pub preset: KeymapPreset,
+    #[serde(default, flatten)]
+    pub overrides: BTreeMap<String, BTreeMap<String, Vec<String>>>,
+}
+
+/// Query history kept for Up/Down recall and Ctrl+R search
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct HistorySettings {
+    /// Name of the history file, so separate setups keep separate histories
+    #[serde(default = "default_history_profile")]
+    pub profile: String,
+    /// Queries kept; 0 turns history off
+    #[serde(default = "default_history_max_entries")]
+    pub max_entries: usize,
+}
+
+impl Default for HistorySettings {
+    fn default() -> Self {
+        Self {
+            profile: default_history_profile(),
+            max_entries: default_history_max_entries(),
+        }
+    }
+}
+
+fn default_history_profile() -> String {
+    "default".to_string()
+}
+
+fn default_history_max_entries() -> usize {
+    history::DEFAULT_MAX_ENTRIES
+}
+
+/// Whether model traffic is recorded, replayed or left alone
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CassetteMode {
+    #[default]
+    Off,
+    Record,
+    Replay,
+}
+
+/// Where and how the record/replay cassette is used
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CassetteSettings {
+    #[serde(default)]
+    pub mode: CassetteMode,
+    #[serde(default = "default_cassette_path")]
+    pub path: String,
+}
+
+impl Default for CassetteSettings {
+    fn default() -> Self {
+        Self {
+            mode: CassetteMode::Off,
+            path: default_cassette_path(),
+        }
+    }
+}
+
+fn default_cassette_path() -> String {
+    "cassette.json".to_string()
+}
+
+/// Per-role request timeout and sampling parameters
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct GenerationSettings {
+    /// Per attempt; a hanging backend is retried up to three times, so with
+    /// backoff a request can take about four times this before failing
+    pub timeout_secs: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Extra Ollama `options` such as `num_ctx`, passed through unchanged
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ollama_options: BTreeMap<String, Value>,
+}
+
+impl GenerationSettings {
+    /// Defaults for the orchestrator role (local proposals)
+    pub fn orchestrator() -> Self {
+        Self {
+            timeout_secs: 120,
+            temperature: Some(0.7),
+            top_p: None,
+            max_tokens: Some(2000),
+            seed: None,
+            ollama_options: BTreeMap::new(),
+        }
+    }
+
+    /// Defaults for the synthesizer role (cloud atomic note)
+    pub fn synthesizer() -> Self {
+        Self {
+            timeout_secs: 30,
+            temperature: None,
+            top_p: None,
+            max_tokens: Some(1024),
+            seed: None,
+            ollama_options: BTreeMap::new(),
+        }
+    }
+
+    /// Client timeout for one attempt; a zero from `config.toml` counts as one
+    /// second rather than failing every request
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs.max(1))
+    }
+
+    /// Render as editable `key=value` pairs, using `-` for unset values
+    pub fn to_compact_string(&self) -> String {
+        let show = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+        let mut parts = vec![
+            format!("timeout={}", self.timeout_secs),
+            format!(
+                "temperature={}",
+                show(self.temperature.map(|v| v.to_string()))
+            ),
+            format!("top_p={}", show(self.top_p.map(|v| v.to_string()))),
+            format!(
+                "max_tokens={}",
+                show(self.max_tokens.map(|v| v.to_string()))
+            ),
+            format!("seed={}", show(self.seed.map(|v| v.to_string()))),
+        ];
+        for (key, value) in &self.ollama_options {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            parts.push(format!("{}={}", key, value));
+        }
+        parts.join(" ")
+    }
+
+    /// Apply `key=value` pairs; unknown keys become Ollama options
+    pub fn apply_compact_string(&mut self, input: &str) -> Result<(), String> {
+        let mut updated = self.clone();
+        // Ollama options missing from the input are removed
+        updated.ollama_options.clear();
+        for pair in input.split_whitespace() {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("Expected key=value, got '{}'", pair))?;
+            match key {
+                "timeout" | "timeout_secs" => {
+                    updated.timeout_secs = parse_optional(key, value)?
+                        .filter(|&secs| secs > 0)
+                        .ok_or_else(|| "A timeout of at least 1 second is required".to_string())?;
+                }
+                "temperature" => updated.temperature = parse_optional(key, value)?,
+                "top_p" => updated.top_p = parse_optional(key, value)?,
+                "max_tokens" => updated.max_tokens = parse_optional(key, value)?,
+                "seed" => updated.seed = parse_optional(key, value)?,
+                _ if value.is_empty() || value == "-" => {}
+                _ => {
+                    let parsed = serde_json::from_str(value)
+                        .unwrap_or_else(|_| Value::String(value.to_string()));
+                    updated.ollama_options.insert(key.to_string(), parsed);
+                }
+            }
+        }
+        *self = updated;
+        Ok(())
+    }
+}
+
+/// Parse an optional value where empty or `-` means unset
+fn parse_optional<T: std::str::FromStr>(key: &str, value: &str) -> Result<Option<T>, String> {
+    if value.is_empty() || value == "-" {
+        return Ok(None);
+    }
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| format!("Invalid value for {}: '{}'", key, value))
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
             theme: ThemeVariant::default(),
+            color_depth: ColorDepthSetting::default(),
+            run_mode: RunMode::default(),
             endpoint: "localhost:11434".to_string(),
+            provider: ProviderSetting::default(),
             local_model: LOCAL_MODEL_PLACEHOLDER.to_string(),
             api_key: API_KEY_PLACEHOLDER.to_string(),
-            cloud_model: CLOUD_MODEL_PLACEHOLDER.to_string(),
+            cloud_model: ModelChain::from(CLOUD_MODEL_PLACEHOLDER),
+            repair_rounds: DEFAULT_REPAIR_ROUNDS,
+            orchestrator: GenerationSettings::orchestrator(),
+            synthesizer: GenerationSettings::synthesizer(),
+            catalog_ttl_secs: DEFAULT_CATALOG_TTL_SECS,
+            mock: MockSettings::default(),
+            cassette: CassetteSettings::default(),
+            keymap: KeymapSettings::default(),
+            history: HistorySettings::default(),
+            mouse: default_mouse(),
         }
     }
 }
@@ -65,14 +569,45 @@ impl Settings {
         fs::write("config.toml", toml_string)
     }
 
+    pub fn catalog_ttl(&self) -> Duration {
+        Duration::from_secs(self.catalog_ttl_secs)
+    }
+
+    /// Enable or disable cloud requests process-wide to match the run mode
+    pub fn enforce_egress_policy(&self) {
+        cloud::set_cloud_egress_enabled(self.run_mode.uses_cloud());
+    }
+
+    /// Drop the cached provider detection and pin the configured provider, if any
+    pub fn apply_provider_setting(&self) {
+        models::reset_provider_cache(&self.endpoint, self.provider.forced());
+        mock::configure((self.provider == ProviderSetting::Mock).then(|| self.mock.clone()));
+    }
+
+    /// Start recording or replaying model traffic as configured
+    pub fn apply_cassette_setting(&self) -> anyhow::Result<()> {
+        cassette::configure(&self.cassette)
+    }
+
     pub fn is_valid(&self) -> Result<(), ValidationError> {
-        if self.local_model == LOCAL_MODEL_PLACEHOLDER {
+        if self.run_mode.uses_local() && self.local_model == LOCAL_MODEL_PLACEHOLDER {
             return Err(ValidationError::LocalModel);
         }
-        if self.cloud_model == CLOUD_MODEL_PLACEHOLDER {
+        if !self.run_mode.uses_cloud() {
+            return Ok(());
+        }
+        if self.cloud_model.primary() == CLOUD_MODEL_PLACEHOLDER {
             return Err(ValidationError::CloudModel);
         }
-        if self.api_key == API_KEY_PLACEHOLDER {
+        // Mock models are answered in-process and replays from the cassette,
+        // so neither needs a key
+        let needs_key = !cassette::replaying()
+            && !self
+                .cloud_model
+                .models()
+                .iter()
+                .all(|m| mock::is_mock_model(m));
+        if needs_key && self.api_key == API_KEY_PLACEHOLDER {
             return Err(ValidationError::ApiKey);
         }
         Ok(())
@@ -85,33 +620,25 @@ impl Settings {
         self.is_valid()?;
 
         // Then validate actual endpoints and generation capabilities
-        validator
-            .validate_local_endpoint(&self.endpoint, &self.local_model)
-            .await
-            .map_err(|_| ValidationError::LocalEndpointUnreachable)?;
-
-        validator
-            .test_local_generation(&self.endpoint, &self.local_model)
-            .await
-            .map_err(|_| ValidationError::LocalEndpointUnreachable)?;
-
-        validator
-            .validate_cloud_endpoint(&self.api_key, &self.cloud_model)
-            .await
-            .map_err(|_| ValidationError::CloudEndpointUnreachable)?;
+        if self.run_mode.uses_local() {
+            self.validate_local_only().await?;
+        }
 
-        validator
-            .test_cloud_generation(&self.api_key, &self.cloud_model)
-            .await
-            .map_err(|_| ValidationError::CloudEndpointUnreachable)?;
+        if !self.run_mode.uses_cloud() {
+            return Ok(());
+        }
 
-        Ok(())
+        self.validate_cloud_chain(&validator).await
     }
 
     pub async fn validate_local_only(&self) -> Result<(), ValidationError> {
         if self.local_model == LOCAL_MODEL_PLACEHOLDER {
             return Err(ValidationError::LocalModel);
         }
+        // Replies come from the cassette, so the endpoint need not exist
+        if cassette::replaying() {
+            return Ok(());
+        }
 
         let validator = ModelValidator::new();
 
@@ -119,7 +646,13 @@ impl Settings {
         validator
             .validate_local_endpoint(&self.endpoint, &self.local_model)
             .await
-            .map_err(|_| ValidationError::LocalEndpointUnreachable)?;
+            .map_err(|e| {
+                if e.is::<ModelNotFoundError>() {
+                    ValidationError::LocalModelNotFound
+                } else {
+                    ValidationError::LocalEndpointUnreachable
+                }
+            })?;
 
         // Then test actual generation capability
         validator
@@ -131,27 +664,53 @@ impl Settings {
     }
 
     pub async fn validate_cloud_only(&self) -> Result<(), ValidationError> {
-        if self.cloud_model == CLOUD_MODEL_PLACEHOLDER {
+        if self.cloud_model.primary() == CLOUD_MODEL_PLACEHOLDER {
             return Err(ValidationError::CloudModel);
         }
-        if self.api_key == API_KEY_PLACEHOLDER {
+        if self.api_key == API_KEY_PLACEHOLDER && !cassette::replaying() {
             return Err(ValidationError::ApiKey);
         }
 
         let validator = ModelValidator::new();
+        self.validate_cloud_chain(&validator).await
+    }
 
-        // First validate the model exists
-        validator
-            .validate_cloud_endpoint(&self.api_key, &self.cloud_model)
-            .await
-            .map_err(|_| ValidationError::CloudEndpointUnreachable)?;
+    /// The cloud side is usable as long as one model in the chain answers
+    async fn validate_cloud_chain(
+        &self,
+        validator: &ModelValidator,
+    ) -> Result<(), ValidationError> {
+        // Replies come from the cassette, so OpenRouter is never asked
+        if cassette::replaying() {
+            return Ok(());
+        }
 
-        // Then test actual generation capability
-        validator
-            .test_cloud_generation(&self.api_key, &self.cloud_model)
-            .await
-            .map_err(|_| ValidationError::CloudEndpointUnreachable)?;
+        let mut all_missing = true;
+        for model in self.cloud_model.models() {
+            // First validate the model exists, then test actual generation capability
+            match validator
+                .validate_cloud_model(&self.api_key, model, self.catalog_ttl())
+                .await
+            {
+                Ok(()) => {
+                    all_missing = false;
+                    if validator
+                        .test_cloud_generation(&self.api_key, model)
+                        .await
+                        .is_ok()
+                    {
+                        return Ok(());
+                    }
+                }
+                Err(e) => all_missing &= e.is::<ModelNotFoundError>(),
+            }
+        }
 
-        Ok(())
+        // Only blame the model ids when OpenRouter answered for every one of them
+        if all_missing {
+            Err(ValidationError::CloudModelNotFound)
+        } else {
+            Err(ValidationError::CloudEndpointUnreachable)
+        }
     }
 }
@@ -1,29 +1,110 @@
 // theme.rs
-//! Everforest Theme System for Agentic
+//! Theme System for Agentic
 //!
-//! Implements a comprehensive theming architecture with Everforest Dark/Light variants.
+//! Built-in palettes (Everforest, Gruvbox, Catppuccin, Nord and a high-contrast
+//! theme) plus user themes read from TOML files in `<config dir>/themes/`.
 //! Provides clean separation of concerns and runtime theme switching capability.
 
+use crate::color_depth::ColorDepth;
+use crate::paths;
+use anyhow::Result;
 use ratatui::style::{Color, Modifier, Style};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+const THEMES_DIR: &str = "themes";
 
 /// Theme variants supported by Agentic
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Stored in `config.toml` by name, so user themes sit alongside the built-ins.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum ThemeVariant {
     /// Everforest Dark theme (default)
+    #[default]
     EverforestDark,
     /// Everforest Light theme
     EverforestLight,
+    GruvboxDark,
+    /// Catppuccin Mocha
+    Catppuccin,
+    Nord,
+    /// Pure black and white with saturated accents
+    HighContrast,
+    /// A theme file `<config dir>/themes/<name>.toml`
+    Custom(String),
 }
 
-impl Default for ThemeVariant {
-    fn default() -> Self {
-        Self::EverforestDark
+impl ThemeVariant {
+    pub const BUILT_IN: [ThemeVariant; 6] = [
+        ThemeVariant::EverforestDark,
+        ThemeVariant::EverforestLight,
+        ThemeVariant::GruvboxDark,
+        ThemeVariant::Catppuccin,
+        ThemeVariant::Nord,
+        ThemeVariant::HighContrast,
+    ];
+
+    /// Name used in `config.toml`
+    pub fn name(&self) -> &str {
+        match self {
+            ThemeVariant::EverforestDark => "EverforestDark",
+            ThemeVariant::EverforestLight => "EverforestLight",
+            ThemeVariant::GruvboxDark => "GruvboxDark",
+            ThemeVariant::Catppuccin => "Catppuccin",
+            ThemeVariant::Nord => "Nord",
+            ThemeVariant::HighContrast => "HighContrast",
+            ThemeVariant::Custom(name) => name,
+        }
+    }
+
+    /// Name shown in the theme picker
+    pub fn label(&self) -> String {
+        match self {
+            ThemeVariant::EverforestDark => "Everforest Dark".to_string(),
+            ThemeVariant::EverforestLight => "Everforest Light".to_string(),
+            ThemeVariant::GruvboxDark => "Gruvbox Dark".to_string(),
+            ThemeVariant::Catppuccin => "Catppuccin Mocha".to_string(),
+            ThemeVariant::Nord => "Nord".to_string(),
+            ThemeVariant::HighContrast => "High Contrast".to_string(),
+            ThemeVariant::Custom(name) => format!("{} (custom)", name),
+        }
+    }
+
+    /// Built-in themes followed by the user themes found on disk
+    pub fn available() -> Vec<ThemeVariant> {
+        let mut variants = Self::BUILT_IN.to_vec();
+        if let Some(dir) = themes_dir() {
+            variants.extend(
+                custom_theme_names(&dir)
+                    .into_iter()
+                    .map(ThemeVariant::Custom),
+            );
+        }
+        variants
+    }
+}
+
+impl From<String> for ThemeVariant {
+    fn from(name: String) -> Self {
+        Self::BUILT_IN
+            .into_iter()
+            .find(|variant| variant.name() == name)
+            .unwrap_or(ThemeVariant::Custom(name))
+    }
+}
+
+impl From<ThemeVariant> for String {
+    fn from(variant: ThemeVariant) -> Self {
+        variant.name().to_string()
     }
 }
 
 /// Color palette for a theme variant
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ColorPalette {
     pub background: Color,
     pub foreground: Color,
@@ -36,8 +117,27 @@ pub struct ColorPalette {
     pub warning: Color, // Yellow/orange for settings
 }
 
+impl ColorPalette {
+    /// The palette slot called `name`, for colors that refer to the palette
+    fn slot(&self, name: &str) -> Option<Color> {
+        Some(match name {
+            "background" => self.background,
+            "foreground" => self.foreground,
+            "accent" => self.accent,
+            "secondary" => self.secondary,
+            "info" => self.info,
+            "border" => self.border,
+            "selection" => self.selection,
+            "cursor" => self.cursor,
+            "warning" => self.warning,
+            _ => return None,
+        })
+    }
+}
+
 /// UI element types for styling
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Element {
     /// Normal text content
     Text,
@@ -63,11 +163,89 @@ pub enum Element {
     Warning,
 }
 
+/// A color in a theme file: `"#rrggbb"`, a name such as `"red"`, a palette
+/// slot such as `"accent"`, or a 256-color index
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum ColorValue {
+    Indexed(u8),
+    Named(String),
+}
+
+impl ColorValue {
+    fn resolve(&self, palette: &ColorPalette) -> Result<Color> {
+        match self {
+            ColorValue::Indexed(index) => Ok(Color::Indexed(*index)),
+            ColorValue::Named(name) => palette
+                .slot(name)
+                .or_else(|| Color::from_str(name).ok())
+                .ok_or_else(|| anyhow::anyhow!("Unknown color '{}'", name)),
+        }
+    }
+}
+
+/// Palette entries of a theme file; missing ones come from the base theme
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PaletteFile {
+    pub background: Option<ColorValue>,
+    pub foreground: Option<ColorValue>,
+    pub accent: Option<ColorValue>,
+    pub secondary: Option<ColorValue>,
+    pub info: Option<ColorValue>,
+    pub border: Option<ColorValue>,
+    pub selection: Option<ColorValue>,
+    pub cursor: Option<ColorValue>,
+    pub warning: Option<ColorValue>,
+}
+
+/// Replaces parts of the style the theme derives for one element
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StyleOverride {
+    pub fg: Option<ColorValue>,
+    pub bg: Option<ColorValue>,
+    pub bold: Option<bool>,
+    pub italic: Option<bool>,
+    pub underlined: Option<bool>,
+}
+
+/// A user theme as written in `<config dir>/themes/<name>.toml`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ThemeFile {
+    /// Built-in theme to start from (default Everforest Dark)
+    #[serde(default)]
+    pub base: Option<ThemeVariant>,
+    #[serde(default)]
+    pub palette: PaletteFile,
+    #[serde(default)]
+    pub styles: BTreeMap<Element, StyleOverride>,
+}
+
+impl ThemeFile {
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        // One line, so it fits the theme picker
+        toml::from_str(&contents).map_err(|e| {
+            let line = e
+                .span()
+                .map(|span| contents[..span.start].matches('\n').count() + 1);
+            match line {
+                Some(line) => anyhow::anyhow!("line {}: {}", line, e.message()),
+                None => anyhow::anyhow!("{}", e.message()),
+            }
+        })
+    }
+}
+
 /// Main theme structure managing all UI styling
 #[derive(Debug, Clone)]
 pub struct Theme {
     variant: ThemeVariant,
+    depth: ColorDepth,
     colors: ColorPalette,
+    overrides: BTreeMap<Element, Style>,
+    load_error: Option<String>, // Why a custom theme fell back to the default
 }
 
 impl Default for Theme {
@@ -77,67 +255,125 @@ impl Default for Theme {
 }
 
 impl Theme {
-    /// Create a new theme with the specified variant
+    /// Create a new theme with the specified variant, for the detected color depth
     pub fn new(variant: ThemeVariant) -> Self {
-        // Check if we're in Terminal.app (which has poor RGB support)
-        let use_basic_colors = std::env::var("TERM_PROGRAM")
-            .map(|term| term == "Apple_Terminal")
-            .unwrap_or(false);
-
-        let colors = match (variant, use_basic_colors) {
-            (ThemeVariant::EverforestDark, false) => ColorPalette {
-                background: Color::Rgb(45, 53, 59),    // #2d353b
-                foreground: Color::Rgb(211, 198, 170), // #d3c6aa
-                accent: Color::Rgb(167, 192, 128),     // #a7c080 (green)
-                secondary: Color::Rgb(230, 126, 128),  // #e67e80 (red)
-                info: Color::Rgb(127, 187, 179),       // #7fbbb3 (aqua)
-                border: Color::Rgb(130, 140, 150),     // #828c96
-                selection: Color::Rgb(64, 72, 78),     // #40484e
-                cursor: Color::Rgb(211, 198, 170),     // #d3c6aa
-                warning: Color::Rgb(219, 188, 127),    // #dbbc7f
-            },
-            (ThemeVariant::EverforestLight, false) => ColorPalette {
-                background: Color::Rgb(253, 246, 227), // #fdf6e3
-                foreground: Color::Rgb(76, 86, 94),    // #4c565e
-                accent: Color::Rgb(141, 161, 1),       // #8da101
-                secondary: Color::Rgb(248, 85, 82),    // #f85552
-                info: Color::Rgb(53, 167, 124),        // #35a77c
-                border: Color::Rgb(150, 160, 170),     // #96a0aa
-                selection: Color::Rgb(243, 236, 217),  // #f3ecd9
-                cursor: Color::Rgb(76, 86, 94),        // #4c565e
-                warning: Color::Rgb(207, 131, 44),     // #cf832c
-            },
-            // Terminal.app fallback colors
-            (ThemeVariant::EverforestDark, true) => ColorPalette {
-                background: Color::Reset, // Use terminal's default background
-                foreground: Color::White,
-                accent: Color::Green,
-                secondary: Color::Red,
-                info: Color::Cyan,
-                border: Color::Gray,    // Gray instead of DarkGray for visibility
-                selection: Color::Blue, // Blue selection for better visibility
-                cursor: Color::White,
-                warning: Color::Yellow,
-            },
-            (ThemeVariant::EverforestLight, true) => ColorPalette {
-                background: Color::Reset, // Use terminal's default background
-                foreground: Color::Black,
-                accent: Color::Green,
-                secondary: Color::Red,
-                info: Color::Blue,       // Blue instead of Cyan for better contrast
-                border: Color::DarkGray, // DarkGray for better contrast
-                selection: Color::Yellow, // Yellow instead of LightYellow
-                cursor: Color::Black,
-                warning: Color::Magenta, // Magenta for warnings to stand out
+        Self::with_depth(variant, ColorDepth::detect())
+    }
+
+    /// Create a theme with its colors reduced to what `depth` can show
+    ///
+    /// A custom theme that cannot be loaded falls back to the default palette
+    /// and keeps the reason in `load_error`.
+    pub fn with_depth(variant: ThemeVariant, depth: ColorDepth) -> Self {
+        let theme = match &variant {
+            ThemeVariant::Custom(name) => Self::load_custom(name).unwrap_or_else(|e| Self {
+                colors: builtin_palette(&ThemeVariant::default()),
+                variant: variant.clone(),
+                depth: ColorDepth::TrueColor,
+                overrides: BTreeMap::new(),
+                load_error: Some(format!("Theme '{}' could not be loaded: {:#}", name, e)),
+            }),
+            _ => Self {
+                colors: builtin_palette(&variant),
+                variant,
+                depth: ColorDepth::TrueColor,
+                overrides: BTreeMap::new(),
+                load_error: None,
             },
         };
+        theme.adapted(depth)
+    }
+
+    /// The same theme with every color mapped to the nearest one `depth` can show
+    fn adapted(self, depth: ColorDepth) -> Self {
+        let adapt = |color| depth.adapt(color);
+        let colors = &self.colors;
+        let colors = ColorPalette {
+            background: adapt(colors.background),
+            foreground: adapt(colors.foreground),
+            accent: adapt(colors.accent),
+            secondary: adapt(colors.secondary),
+            info: adapt(colors.info),
+            border: adapt(colors.border),
+            selection: adapt(colors.selection),
+            cursor: adapt(colors.cursor),
+            warning: adapt(colors.warning),
+        };
+        let overrides = self
+            .overrides
+            .into_iter()
+            .map(|(element, mut style)| {
+                style.fg = style.fg.map(adapt);
+                style.bg = style.bg.map(adapt);
+                (element, style)
+            })
+            .collect();
+        Self {
+            variant: self.variant,
+            depth,
+            colors,
+            overrides,
+            load_error: self.load_error,
+        }
+    }
+
+    /// Load `<config dir>/themes/<name>.toml`
+    pub fn load_custom(name: &str) -> Result<Self> {
+        if !is_valid_theme_name(name) {
+            return Err(anyhow::anyhow!(
+                "Theme names are file names without a path, not '{}'",
+                name
+            ));
+        }
+        let dir = themes_dir().ok_or_else(|| anyhow::anyhow!("No config directory"))?;
+        let file = ThemeFile::load_from(&dir.join(format!("{}.toml", name)))?;
+        Self::from_file(name, &file)
+    }
+
+    /// Build a theme from a parsed theme file
+    pub fn from_file(name: &str, file: &ThemeFile) -> Result<Self> {
+        let base = match &file.base {
+            Some(ThemeVariant::Custom(base)) => {
+                return Err(anyhow::anyhow!("Unknown base theme '{}'", base))
+            }
+            Some(base) => builtin_palette(base),
+            None => builtin_palette(&ThemeVariant::default()),
+        };
+
+        let pick = |value: &Option<ColorValue>, fallback: Color| match value {
+            Some(value) => value.resolve(&base),
+            None => Ok(fallback),
+        };
+        let palette = &file.palette;
+        let colors = ColorPalette {
+            background: pick(&palette.background, base.background)?,
+            foreground: pick(&palette.foreground, base.foreground)?,
+            accent: pick(&palette.accent, base.accent)?,
+            secondary: pick(&palette.secondary, base.secondary)?,
+            info: pick(&palette.info, base.info)?,
+            border: pick(&palette.border, base.border)?,
+            selection: pick(&palette.selection, base.selection)?,
+            cursor: pick(&palette.cursor, base.cursor)?,
+            warning: pick(&palette.warning, base.warning)?,
+        };
 
-        Self { variant, colors }
+        let mut overrides = BTreeMap::new();
+        for (element, style) in &file.styles {
+            overrides.insert(*element, style_override(style, &colors)?);
+        }
+
+        Ok(Self {
+            variant: ThemeVariant::Custom(name.to_string()),
+            depth: ColorDepth::TrueColor,
+            colors,
+            overrides,
+            load_error: None,
+        })
     }
 
     /// Get the current theme variant
     pub fn variant(&self) -> ThemeVariant {
-        self.variant
+        self.variant.clone()
     }
 
     /// Get the color palette
@@ -145,25 +381,38 @@ impl Theme {
         &self.colors
     }
 
-    /// Toggle between dark and light variants
-    pub fn toggle(&mut self) {
-        self.variant = match self.variant {
-            ThemeVariant::EverforestDark => ThemeVariant::EverforestLight,
-            ThemeVariant::EverforestLight => ThemeVariant::EverforestDark,
-        };
-        *self = Self::new(self.variant);
+    /// Get the color depth the palette was reduced to
+    pub fn depth(&self) -> ColorDepth {
+        self.depth
+    }
+
+    /// Why the custom theme file couldn't be used, if it couldn't
+    pub fn load_error(&self) -> Option<&str> {
+        self.load_error.as_deref()
     }
 
     /// Set specific theme variant
     pub fn set_variant(&mut self, variant: ThemeVariant) {
         if self.variant != variant {
-            self.variant = variant;
-            *self = Self::new(self.variant);
+            *self = Self::with_depth(variant, self.depth);
         }
     }
 
     /// Get a ratatui Style for the specified UI element
     pub fn ratatui_style(&self, element: Element) -> Style {
+        let mut style = self.base_style(element);
+        // Without colors, selection backgrounds vanish; reverse video stands in
+        if self.depth == ColorDepth::NoColor && element == Element::Highlight {
+            style = style.add_modifier(Modifier::REVERSED);
+        }
+        match self.overrides.get(&element) {
+            Some(patch) => style.patch(*patch),
+            None => style,
+        }
+    }
+
+    /// The style the palette gives an element, before overrides
+    fn base_style(&self, element: Element) -> Style {
         match element {
             Element::Text => Style::default()
                 .fg(self.colors.foreground)
@@ -217,6 +466,9 @@ impl Theme {
 
     /// Get foreground color for an element
     pub fn fg_color(&self, element: Element) -> Color {
+        if let Some(fg) = self.overrides.get(&element).and_then(|style| style.fg) {
+            return fg;
+        }
         match element {
             Element::Text | Element::Background => self.colors.foreground,
             Element::Title | Element::Accent | Element::Active => self.colors.accent,
@@ -230,12 +482,21 @@ impl Theme {
 
     /// Get background color for an element
     pub fn bg_color(&self, element: Element) -> Color {
+        if let Some(bg) = self.overrides.get(&element).and_then(|style| style.bg) {
+            return bg;
+        }
         match element {
             Element::Highlight | Element::Active => self.colors.selection,
             _ => self.colors.background,
         }
     }
 
+    /// Get style for the dimmed backdrop behind modals
+    pub fn backdrop_style(&self) -> Style {
+        self.ratatui_style(Element::Background)
+            .bg(self.depth.adapt(Color::Rgb(20, 20, 20)))
+    }
+
     /// Get style for block titles
     pub fn title_style(&self) -> Style {
         self.ratatui_style(Element::Title)
@@ -276,3 +537,126 @@ impl Theme {
         self.ratatui_style(Element::Warning)
     }
 }
+
+fn style_override(style: &StyleOverride, palette: &ColorPalette) -> Result<Style> {
+    let mut patch = Style::default();
+    if let Some(fg) = &style.fg {
+        patch = patch.fg(fg.resolve(palette)?);
+    }
+    if let Some(bg) = &style.bg {
+        patch = patch.bg(bg.resolve(palette)?);
+    }
+    for (flag, modifier) in [
+        (style.bold, Modifier::BOLD),
+        (style.italic, Modifier::ITALIC),
+        (style.underlined, Modifier::UNDERLINED),
+    ] {
+        patch = match flag {
+            Some(true) => patch.add_modifier(modifier),
+            Some(false) => patch.remove_modifier(modifier),
+            None => patch,
+        };
+    }
+    Ok(patch)
+}
+
+/// Whether `name` stays inside the themes directory once joined to it
+pub fn is_valid_theme_name(name: &str) -> bool {
+    paths::is_plain_file_name(name)
+}
+
+/// Where user theme files live
+pub fn themes_dir() -> Option<PathBuf> {
+    paths::config_dir().map(|dir| dir.join(THEMES_DIR))
+}
+
+/// Names of the `*.toml` files in `dir`, sorted
+fn custom_theme_names(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        // A file named after a built-in would be unreachable
+        .filter(|name| {
+            ThemeVariant::BUILT_IN
+                .iter()
+                .all(|builtin| builtin.name() != name)
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+fn builtin_palette(variant: &ThemeVariant) -> ColorPalette {
+    match variant {
+        ThemeVariant::EverforestDark | ThemeVariant::Custom(_) => ColorPalette {
+            background: Color::Rgb(45, 53, 59),    // #2d353b
+            foreground: Color::Rgb(211, 198, 170), // #d3c6aa
+            accent: Color::Rgb(167, 192, 128),     // #a7c080 (green)
+            secondary: Color::Rgb(230, 126, 128),  // #e67e80 (red)
+            info: Color::Rgb(127, 187, 179),       // #7fbbb3 (aqua)
+            border: Color::Rgb(130, 140, 150),     // #828c96
+            selection: Color::Rgb(64, 72, 78),     // #40484e
+            cursor: Color::Rgb(211, 198, 170),     // #d3c6aa
+            warning: Color::Rgb(219, 188, 127),    // #dbbc7f
+        },
+        ThemeVariant::EverforestLight => ColorPalette {
+            background: Color::Rgb(253, 246, 227), // #fdf6e3
+            foreground: Color::Rgb(76, 86, 94),    // #4c565e
+            accent: Color::Rgb(141, 161, 1),       // #8da101
+            secondary: Color::Rgb(248, 85, 82),    // #f85552
+            info: Color::Rgb(53, 167, 124),        // #35a77c
+            border: Color::Rgb(150, 160, 170),     // #96a0aa
+            selection: Color::Rgb(243, 236, 217),  // #f3ecd9
+            cursor: Color::Rgb(76, 86, 94),        // #4c565e
+            warning: Color::Rgb(207, 131, 44),     // #cf832c
+        },
+        ThemeVariant::GruvboxDark => ColorPalette {
+            background: Color::Rgb(40, 40, 40),    // #282828
+            foreground: Color::Rgb(235, 219, 178), // #ebdbb2
+            accent: Color::Rgb(184, 187, 38),      // #b8bb26 (green)
+            secondary: Color::Rgb(251, 73, 52),    // #fb4934 (red)
+            info: Color::Rgb(142, 192, 124),       // #8ec07c (aqua)
+            border: Color::Rgb(146, 131, 116),     // #928374
+            selection: Color::Rgb(60, 56, 54),     // #3c3836
+            cursor: Color::Rgb(235, 219, 178),     // #ebdbb2
+            warning: Color::Rgb(250, 189, 47),     // #fabd2f
+        },
+        ThemeVariant::Catppuccin => ColorPalette {
+            background: Color::Rgb(30, 30, 46),    // #1e1e2e (base)
+            foreground: Color::Rgb(205, 214, 244), // #cdd6f4 (text)
+            accent: Color::Rgb(203, 166, 247),     // #cba6f7 (mauve)
+            secondary: Color::Rgb(243, 139, 168),  // #f38ba8 (red)
+            info: Color::Rgb(137, 220, 235),       // #89dceb (sky)
+            border: Color::Rgb(127, 132, 156),     // #7f849c (overlay1)
+            selection: Color::Rgb(49, 50, 68),     // #313244 (surface0)
+            cursor: Color::Rgb(245, 224, 220),     // #f5e0dc (rosewater)
+            warning: Color::Rgb(249, 226, 175),    // #f9e2af (yellow)
+        },
+        ThemeVariant::Nord => ColorPalette {
+            background: Color::Rgb(46, 52, 64),    // #2e3440 (nord0)
+            foreground: Color::Rgb(216, 222, 233), // #d8dee9 (nord4)
+            accent: Color::Rgb(136, 192, 208),     // #88c0d0 (nord8)
+            secondary: Color::Rgb(191, 97, 106),   // #bf616a (nord11)
+            info: Color::Rgb(129, 161, 193),       // #81a1c1 (nord9)
+            border: Color::Rgb(97, 110, 136),      // #616e88
+            selection: Color::Rgb(67, 76, 94),     // #434c5e (nord2)
+            cursor: Color::Rgb(216, 222, 233),     // #d8dee9
+            warning: Color::Rgb(235, 203, 139),    // #ebcb8b (nord13)
+        },
+        ThemeVariant::HighContrast => ColorPalette {
+            background: Color::Rgb(0, 0, 0),
+            foreground: Color::Rgb(255, 255, 255),
+            accent: Color::Rgb(0, 255, 135),    // #00ff87
+            secondary: Color::Rgb(255, 95, 95), // #ff5f5f
+            info: Color::Rgb(95, 215, 255),     // #5fd7ff
+            border: Color::Rgb(255, 255, 255),
+            selection: Color::Rgb(0, 0, 175), // #0000af
+            cursor: Color::Rgb(255, 255, 255),
+            warning: Color::Rgb(255, 215, 0), // #ffd700
+        },
+    }
+}
@@ -0,0 +1,95 @@
+//! Replaying a cassette end to end with no backend reachable.
+//!
+//! The cassette is process-wide state, so this lives in its own test binary
+//! rather than beside the unit tests in `lib.rs`.
+
+use agentic_core::cassette::{self, Backend, Cassette, Exchange};
+use agentic_core::cloud::call_cloud_model;
+use agentic_core::orchestrator::generate_proposals;
+use agentic_core::settings::{
+    CassetteMode, CassetteSettings, GenerationSettings, ModelChain, Settings,
+};
+
+#[tokio::test]
+async fn test_replay_without_network() {
+    // Any request that slips past the cassette fails instead of going online
+    for proxy in ["HTTP_PROXY", "HTTPS_PROXY", "ALL_PROXY"] {
+        std::env::set_var(proxy, "http://127.0.0.1:9");
+    }
+
+    let exchange = |backend, model: &str, response: &str| Exchange {
+        backend,
+        model: model.to_string(),
+        prompt: "recorded elsewhere".to_string(),
+        response: response.to_string(),
+    };
+    let cassette = Cassette {
+        version: 1,
+        exchanges: vec![
+            // The bad output that was reported, then the model's repair
+            exchange(
+                Backend::Local,
+                "llama3.2:3b",
+                "{\"proposals\": [\"unterminated",
+            ),
+            exchange(
+                Backend::Local,
+                "llama3.2:3b",
+                "{\"proposals\": [\"first\", \"second\", \"third\"]}",
+            ),
+            exchange(
+                Backend::Cloud,
+                "deepseek/deepseek-chat:free",
+                "{\"header_tags\": [\"replay\"], \"body_text\": \"From the cassette\"}",
+            ),
+        ],
+    };
+    let path = std::env::temp_dir().join(format!(
+        "ruixen-cassette-replay-{}.json",
+        std::process::id()
+    ));
+    cassette.save_to(&path).unwrap();
+
+    // Nothing listens on the discard port, and there is no API key
+    let settings = Settings {
+        endpoint: "127.0.0.1:9".to_string(),
+        local_model: "llama3.2:3b".to_string(),
+        cloud_model: ModelChain::from("deepseek/deepseek-chat:free"),
+        cassette: CassetteSettings {
+            mode: CassetteMode::Replay,
+            path: path.display().to_string(),
+        },
+        ..Settings::default()
+    };
+    settings.apply_cassette_setting().unwrap();
+    std::fs::remove_file(&path).ok();
+    assert!(cassette::replaying());
+
+    assert_eq!(settings.is_valid(), Ok(()));
+    assert_eq!(settings.validate_endpoints().await, Ok(()));
+    assert_eq!(settings.validate_local_only().await, Ok(()));
+    assert_eq!(settings.validate_cloud_only().await, Ok(()));
+
+    let proposals = generate_proposals(
+        "why is the sky blue",
+        &settings.endpoint,
+        &settings.local_model,
+        1,
+        &GenerationSettings::orchestrator(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(proposals, ["first", "second", "third"]);
+
+    let synthesis = call_cloud_model(
+        &settings.api_key,
+        settings.cloud_model.models(),
+        &proposals[0],
+        0,
+        &GenerationSettings::synthesizer(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(synthesis.note.header_tags, ["replay"]);
+    assert_eq!(synthesis.note.body_text, "From the cassette");
+}
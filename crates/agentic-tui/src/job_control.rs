@@ -0,0 +1,78 @@
+use std::io;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobSignal {
+    Stop,    // SIGTSTP, e.g. `kill -TSTP`
+    Resumed, // SIGCONT
+}
+
+/// SIGTSTP and SIGCONT as they arrive
+///
+/// Catching SIGTSTP means it no longer stops the process by itself, so the
+/// terminal can be handed back to the shell first.
+#[derive(Debug, Default)]
+pub struct JobSignals {
+    #[cfg(unix)]
+    stop: Option<tokio::signal::unix::Signal>,
+    #[cfg(unix)]
+    resumed: Option<tokio::signal::unix::Signal>,
+    own_stop: bool, // The next SIGCONT ends a stop we made ourselves
+}
+
+impl JobSignals {
+    /// Start catching the signals; without them (e.g. on Windows) nothing arrives
+    pub fn register() -> Self {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+            Self {
+                stop: signal(SignalKind::from_raw(signal_hook::consts::SIGTSTP)).ok(),
+                resumed: signal(SignalKind::from_raw(signal_hook::consts::SIGCONT)).ok(),
+                own_stop: false,
+            }
+        }
+        #[cfg(not(unix))]
+        Self::default()
+    }
+
+    /// Stop the process once the terminal is handed back; `recv` skips the
+    /// SIGCONT that continues it
+    pub fn stop(&mut self) -> io::Result<()> {
+        self.own_stop = true;
+        stop_self()
+    }
+
+    /// Wait for the next signal
+    pub async fn recv(&mut self) -> JobSignal {
+        #[cfg(unix)]
+        loop {
+            tokio::select! {
+                Some(()) = recv_from(&mut self.stop) => return JobSignal::Stop,
+                Some(()) = recv_from(&mut self.resumed) => {
+                    if !std::mem::take(&mut self.own_stop) {
+                        return JobSignal::Resumed;
+                    }
+                }
+                else => std::future::pending::<()>().await,
+            }
+        }
+        #[cfg(not(unix))]
+        std::future::pending().await
+    }
+}
+
+#[cfg(unix)]
+async fn recv_from(signal: &mut Option<tokio::signal::unix::Signal>) -> Option<()> {
+    match signal {
+        Some(signal) => signal.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Stop the process as an uncaught SIGTSTP would; returns once the shell
+/// continues it
+fn stop_self() -> io::Result<()> {
+    #[cfg(unix)]
+    signal_hook::low_level::emulate_default_handler(signal_hook::consts::SIGTSTP)?;
+    Ok(())
+}
@@ -1,11 +1,14 @@
-use agentic_core::settings::Settings;
+use agentic_core::{crash, settings::Settings};
 use anyhow::Result;
 use crossterm::{
+    cursor::Show,
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::prelude::{CrosstermBackend, Terminal};
-use std::io::{stdout, Stdout};
+use std::io::{stdout, Stdout, Write};
+mod job_control;
 mod ui;
 use ui::app::App;
 
@@ -18,7 +21,14 @@ async fn main() -> Result<()> {
             Settings::default()
         }
     };
-    let mut terminal = init_terminal()?;
+    if let Err(e) = settings.apply_cassette_setting() {
+        eprintln!(
+            "Warning: Failed to load cassette: {}. Using live models.",
+            e
+        );
+    }
+    install_panic_hook();
+    let mut terminal = init_terminal(settings.mouse)?;
     let mut app = App::new(settings);
 
     let result = app.run(&mut terminal).await;
@@ -28,18 +38,85 @@ async fn main() -> Result<()> {
     result
 }
 
-fn init_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
-    enable_raw_mode()?;
+fn init_terminal(mouse: bool) -> Result<Terminal<CrosstermBackend<Stdout>>> {
     let mut stdout = stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    enter_tui(&mut stdout, mouse)?;
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::new(backend)?;
     Ok(terminal)
 }
 
+/// Take the terminal back after `restore_terminal`, e.g. when an editor exits
+fn resume_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>, mouse: bool) -> Result<()> {
+    enter_tui(terminal.backend_mut(), mouse)?;
+    // The other program drew over the screen; repaint all of it
+    terminal.clear()?;
+    Ok(())
+}
+
+/// Give the shell its terminal back and stop, like Ctrl+Z outside raw mode
+fn suspend_terminal(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    job_signals: &mut job_control::JobSignals,
+    mouse: bool,
+) -> Result<()> {
+    restore_terminal(terminal)?;
+    job_signals.stop()?;
+    resume_terminal(terminal, mouse)
+}
+
+/// Leave the shell usable if the UI panics, and keep a crash report
+fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+        let report = crash::crash_dir().and_then(|dir| {
+            crash::write_report(
+                &dir,
+                env!("CARGO_PKG_VERSION"),
+                &info.to_string(),
+                &backtrace,
+            )
+            .ok()
+        });
+
+        // Background tasks run on worker threads and tokio recovers from their
+        // panics; only the UI thread owns the terminal
+        if std::thread::current().name() != Some("main") {
+            return;
+        }
+        let _ = disable_raw_mode();
+        let _ = execute!(
+            stdout(),
+            DisableMouseCapture,
+            DisableBracketedPaste,
+            LeaveAlternateScreen,
+            Show
+        );
+        default_hook(info);
+        if let Some(path) = report {
+            eprintln!("Crash report written to {}", path.display());
+        }
+    }));
+}
+
+fn enter_tui(out: &mut impl Write, mouse: bool) -> Result<()> {
+    enable_raw_mode()?;
+    execute!(out, EnterAlternateScreen, EnableBracketedPaste)?;
+    if mouse {
+        execute!(out, EnableMouseCapture)?;
+    }
+    Ok(())
+}
+
 fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        DisableMouseCapture,
+        DisableBracketedPaste,
+        LeaveAlternateScreen
+    )?;
     terminal.show_cursor()?;
     Ok(())
 }
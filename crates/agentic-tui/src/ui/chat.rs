@@ -152,7 +152,15 @@ pub fn render_chat(
             // Status-based message - show one error at a time, prioritizing local > cloud > api key
             let (status_text, status_style) = match agent_status {
                 AgentStatus::LocalEndpointError => (
-                    "Local not ready - see [S]ettings",
+                    "Local not ready - see [S]ettings or [H]ealth",
+                    theme.ratatui_style(Element::Warning),
+                ),
+                AgentStatus::LocalModelNotFound => (
+                    "Local model not found - pick another in [S]ettings",
+                    theme.ratatui_style(Element::Warning),
+                ),
+                AgentStatus::CloudModelNotFound => (
+                    "Cloud model not found - pick another in [S]ettings",
                     theme.ratatui_style(Element::Warning),
                 ),
                 AgentStatus::CheckLocalModel => (
@@ -160,7 +168,7 @@ pub fn render_chat(
                     theme.ratatui_style(Element::Warning),
                 ),
                 AgentStatus::CloudEndpointError => (
-                    "Cloud not ready - see [S]ettings",
+                    "Cloud not ready - see [S]ettings or [H]ealth",
                     theme.ratatui_style(Element::Warning),
                 ),
                 AgentStatus::CheckCloudModel => (
@@ -179,6 +187,10 @@ pub fn render_chat(
                     "🔄 Validating cloud endpoint...",
                     theme.ratatui_style(Element::Info),
                 ),
+                AgentStatus::Cancelled => (
+                    "Request cancelled - press [ENTER] to continue",
+                    theme.ratatui_style(Element::Info),
+                ),
                 _ => (
                     "Press [ENTER] to start Ruixen",
                     theme.ratatui_style(Element::Accent),
@@ -0,0 +1,23 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use std::io::{self, Write};
+
+/// Put `text` on the system clipboard with an OSC 52 escape sequence
+///
+/// The terminal does the copying, so this works over SSH and needs no
+/// clipboard daemon. Terminals don't reply, so `Ok` only means it was sent.
+pub fn copy(text: &str) -> io::Result<()> {
+    let tmux = std::env::var_os("TMUX").is_some();
+    let mut stdout = io::stdout();
+    stdout.write_all(sequence(text, tmux).as_bytes())?;
+    stdout.flush()
+}
+
+fn sequence(text: &str, tmux: bool) -> String {
+    let osc = format!("\x1b]52;c;{}\x07", STANDARD.encode(text));
+    if !tmux {
+        return osc;
+    }
+    // tmux takes the plain sequence with `set-clipboard on` and hands the
+    // wrapped one to the outer terminal with `allow-passthrough on`
+    format!("{}\x1bPtmux;{}\x1b\\", osc, osc.replace('\x1b', "\x1b\x1b"))
+}
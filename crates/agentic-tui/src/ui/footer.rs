@@ -1,4 +1,6 @@
 use crate::ui::app::AppMode;
+use crate::ui::input::LineEditor;
+use crate::ui::keymap::{Action, Bindings, Keymap, NormalAction, NoteAction};
 use agentic_core::theme::{Element, Theme};
 use ratatui::{
     prelude::{Alignment, Frame, Rect},
@@ -6,12 +8,16 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn render_footer(
     frame: &mut Frame,
     area: Rect,
     theme: &Theme,
     mode: AppMode,
-    chat_input: &str,
+    chat_input: &LineEditor,
+    history_search: Option<(&str, Option<&str>)>, // Ctrl+R query and its match
+    keymap: &Keymap,
+    notice: Option<&str>, // Shown instead of the hints for a moment, e.g. after copying
 ) {
     let footer_block = Block::default()
         .borders(Borders::ALL)
@@ -19,57 +25,107 @@ pub fn render_footer(
 
     let inner_area = footer_block.inner(area);
 
+    if let Some(notice) = notice.filter(|_| mode != AppMode::Chat) {
+        let line = Line::from(Span::styled(notice, theme.ratatui_style(Element::Accent)))
+            .alignment(Alignment::Center);
+        frame.render_widget(footer_block, area);
+        frame.render_widget(Paragraph::new(line), inner_area);
+        return;
+    }
+
     let content = match mode {
         AppMode::Complete => {
             // Save/Discard navigation for synthesis results with scroll controls
+            let note = &keymap.note;
             Line::from(vec![
-                Span::raw("[↑] "),
+                Span::raw(format!("[{}] ", note.label(NoteAction::Save))),
                 Span::styled("Save", theme.ratatui_style(Element::Accent)),
                 Span::raw("  |  "),
-                Span::raw("[↓] "),
+                Span::raw(format!("[{}] ", note.label(NoteAction::Discard))),
                 Span::styled("Discard", theme.ratatui_style(Element::Inactive)),
                 Span::raw("  |  "),
-                Span::raw("[←→] "),
+                Span::raw(format!(
+                    "[{}{}] ",
+                    note.label(NoteAction::ScrollUp),
+                    note.label(NoteAction::ScrollDown)
+                )),
                 Span::styled("Scroll", theme.ratatui_style(Element::Text)),
+                Span::raw("  |  "),
+                Span::raw(format!("[{}] ", note.label(NoteAction::Copy))),
+                Span::styled("Copy", theme.ratatui_style(Element::Text)),
+                Span::raw("  |  "),
+                Span::raw(format!("[{}] ", note.label(NoteAction::Help))),
+                Span::styled("Help", theme.ratatui_style(Element::Text)),
             ])
             .alignment(Alignment::Center)
         }
         AppMode::Chat => {
-            // Chat input field with cursor
-            let display_text = if chat_input.is_empty() {
-                "Share your query with Ruixen... (/settings, /quit)"
+            // Chat input field with cursor, growing to several lines
+            let prompt = Span::styled("💬 ", theme.ratatui_style(Element::Accent));
+            let text_width = inner_area.width.saturating_sub(prompt.width() as u16);
+            let mut first_row = 0;
+            let lines = if let Some((query, found)) = history_search {
+                vec![history_search_line(theme, query, found)]
+            } else if chat_input.is_empty() {
+                vec![Line::from(vec![
+                    Span::styled(" ", theme.highlight_style()),
+                    Span::styled(
+                        "Share your query with Ruixen... (/settings, /status, /quit)",
+                        theme.ratatui_style(Element::Inactive),
+                    ),
+                ])]
             } else {
-                chat_input
+                first_row = chat_input.first_visible_row(inner_area.height);
+                chat_input.lines(
+                    text_width,
+                    inner_area.height,
+                    theme.text_style(),
+                    theme.highlight_style(),
+                    false,
+                )
             };
 
-            let mut spans = vec![
-                Span::styled("💬 ", theme.ratatui_style(Element::Accent)),
-                Span::styled(display_text, theme.text_style()),
-            ];
-
-            // Add cursor when in chat mode
-            if !chat_input.is_empty() || area.width > 50 {
-                spans.push(Span::styled("_", theme.highlight_style()));
-            }
+            // The prompt marks the first line of the text, which may be
+            // scrolled out of view; other rows line up under the text
+            let indent = " ".repeat(prompt.width());
+            let lines: Vec<Line> = lines
+                .into_iter()
+                .enumerate()
+                .map(|(row, mut line)| {
+                    let lead = if first_row + row == 0 {
+                        prompt.clone()
+                    } else {
+                        Span::raw(indent.clone())
+                    };
+                    line.spans.insert(0, lead);
+                    line
+                })
+                .collect();
 
-            Line::from(spans)
+            frame.render_widget(footer_block, area);
+            frame.render_widget(
+                Paragraph::new(lines).style(theme.ratatui_style(Element::Text)),
+                inner_area,
+            );
+            return;
         }
         _ => {
             // Navigation bar for Normal mode
-            Line::from(vec![
-                Span::raw("[A]"),
-                Span::styled("bout", theme.ratatui_style(Element::Inactive)),
-                Span::raw(" | "),
-                Span::raw("[S]"),
-                Span::styled("ettings", theme.ratatui_style(Element::Inactive)),
-                Span::raw(" | "),
-                Span::raw("[T]"),
-                Span::styled("heme", theme.ratatui_style(Element::Inactive)),
-                Span::raw(" | "),
-                Span::raw("[Q]"),
-                Span::styled("uit", theme.ratatui_style(Element::Inactive)),
-            ])
-            .alignment(Alignment::Center)
+            let mut spans = Vec::new();
+            for (action, label) in [
+                (NormalAction::About, "About"),
+                (NormalAction::Health, "Health"),
+                (NormalAction::Settings, "Settings"),
+                (NormalAction::Theme, "Theme"),
+                (NormalAction::Quit, "Quit"),
+                (NormalAction::Help, "Help"),
+            ] {
+                if !spans.is_empty() {
+                    spans.push(Span::raw(" | "));
+                }
+                spans.extend(hint(&keymap.normal, action, label, theme));
+            }
+            Line::from(spans).alignment(Alignment::Center)
         }
     };
 
@@ -78,3 +134,57 @@ pub fn render_footer(
     frame.render_widget(footer_block, area);
     frame.render_widget(footer_paragraph, inner_area);
 }
+
+/// `[S]ettings` when the key is the label's first letter, else `[F2] Settings`
+fn hint<'a, A: Action>(
+    bindings: &Bindings<A>,
+    action: A,
+    label: &'a str,
+    theme: &Theme,
+) -> Vec<Span<'a>> {
+    let style = theme.ratatui_style(Element::Inactive);
+    let first = label.chars().next();
+    let shortcut = bindings.keys(action).iter().find(|chord| {
+        chord.char().map(|c| c.to_ascii_lowercase()) == first.map(|c| c.to_ascii_lowercase())
+    });
+    match (shortcut, first) {
+        (Some(_), Some(first)) => vec![
+            Span::raw(format!("[{}]", first.to_ascii_uppercase())),
+            Span::styled(&label[first.len_utf8()..], style),
+        ],
+        _ => vec![
+            Span::raw(format!("[{}] ", bindings.label(action))),
+            Span::styled(label, style),
+        ],
+    }
+}
+
+/// `(search) 'query': match`, with the matched part highlighted
+fn history_search_line<'a>(theme: &Theme, query: &'a str, found: Option<&'a str>) -> Line<'a> {
+    let mut spans = vec![
+        Span::styled("(search) '", theme.ratatui_style(Element::Inactive)),
+        Span::styled(query, theme.ratatui_style(Element::Accent)),
+        Span::styled("': ", theme.ratatui_style(Element::Inactive)),
+    ];
+    match found {
+        Some(entry) => {
+            // Only the first line of a multi-line query fits
+            let entry = entry.lines().next().unwrap_or("");
+            match entry.find(query).filter(|_| !query.is_empty()) {
+                Some(start) => {
+                    let end = start + query.len();
+                    spans.push(Span::styled(&entry[..start], theme.text_style()));
+                    spans.push(Span::styled(&entry[start..end], theme.highlight_style()));
+                    spans.push(Span::styled(&entry[end..], theme.text_style()));
+                }
+                None => spans.push(Span::styled(entry, theme.text_style())),
+            }
+        }
+        None if query.is_empty() => {}
+        None => spans.push(Span::styled(
+            "no match",
+            theme.ratatui_style(Element::Warning),
+        )),
+    }
+    Line::from(spans)
+}
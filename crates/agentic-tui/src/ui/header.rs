@@ -25,7 +25,10 @@ pub fn render_header(
     let (status_text, status_color) =
         build_smart_status_with_color(status, settings, local_tokens, cloud_tokens);
 
-    let status_span = Span::styled(status_text, Style::default().fg(status_color));
+    let status_span = Span::styled(
+        status_text,
+        Style::default().fg(theme.depth().adapt(status_color)),
+    );
 
     let header_paragraph = Paragraph::new(status_span)
         .style(theme.ratatui_style(Element::Text))
@@ -47,18 +50,25 @@ fn build_smart_status_with_color(
     cloud_tokens: u32,
 ) -> (String, Color) {
     // Show actual configuration state with model names always visible
-    let local_configured = settings.local_model != "[SELECT]";
-    let cloud_configured =
-        settings.cloud_model != "[SELECT]" && settings.api_key != "sk-or-v1-982...b52";
+    let cloud_only = !settings.run_mode.uses_local();
+    let local_configured = cloud_only || settings.local_model != "[SELECT]";
+    let local_only = !settings.run_mode.uses_cloud();
+    let cloud_configured = local_only
+        || (settings.cloud_model != "[SELECT]" && settings.api_key != "sk-or-v1-982...b52");
 
-    let local_display = if local_configured {
+    let local_display = if cloud_only {
+        "[CLOUD-ONLY]"
+    } else if local_configured {
         &settings.local_model
     } else {
         "NOT-READY"
     };
 
-    let cloud_display = if cloud_configured {
-        &settings.cloud_model
+    let cloud_chain = settings.cloud_model.to_string();
+    let cloud_display = if local_only {
+        "[LOCAL-ONLY]"
+    } else if cloud_configured {
+        &cloud_chain
     } else {
         "NOT-READY"
     };
@@ -136,6 +146,18 @@ fn build_smart_status_with_color(
                 local_display, cloud_display
             )
         }
+        AgentStatus::LocalModelNotFound => {
+            format!(
+                "Ruixen :: [ERROR: {} NOT FOUND] :: {}",
+                local_display, cloud_display
+            )
+        }
+        AgentStatus::CloudModelNotFound => {
+            format!(
+                "Ruixen :: {} :: [ERROR: {} NOT FOUND]",
+                local_display, cloud_display
+            )
+        }
         AgentStatus::Orchestrating => {
             // Show local tokens during orchestration
             format!(
@@ -154,6 +176,12 @@ fn build_smart_status_with_color(
                 format_token_display(local_tokens, cloud_tokens, status)
             )
         }
+        AgentStatus::Cancelled => {
+            format!(
+                "Ruixen :: {} :: {} :: [CANCELLED]",
+                local_display, cloud_display
+            )
+        }
         AgentStatus::Complete => {
             // Show total bill (red color applied later)
             format!(
@@ -179,7 +207,9 @@ fn build_smart_status_with_color(
         AgentStatus::Orchestrating => Color::Cyan,
         AgentStatus::Searching => Color::Blue, // Cloud synthesis in progress
         AgentStatus::Complete => Color::Green, // Success! (token styling handled separately)
+        AgentStatus::Cancelled => Color::Yellow, // User aborted the request
         AgentStatus::LocalEndpointError | AgentStatus::CloudEndpointError => Color::Red, // Connection failed
+        AgentStatus::LocalModelNotFound | AgentStatus::CloudModelNotFound => Color::Red, // Model missing
         _ => Color::Red, // Other validation failed
     };
 
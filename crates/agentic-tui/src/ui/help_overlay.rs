@@ -0,0 +1,77 @@
+use agentic_core::theme::{Element, Theme};
+use ratatui::{
+    prelude::{Alignment, Constraint, Direction, Frame, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Key bindings of the current mode, drawn over whatever is on screen
+pub fn render_help_overlay(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    title: &str,
+    rows: &[(String, &str)],
+    errors: &[String],
+) {
+    let key_width = rows
+        .iter()
+        .map(|(keys, _)| keys.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut lines: Vec<Line> = rows
+        .iter()
+        .map(|(keys, description)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:>width$}  ", keys, width = key_width),
+                    theme.ratatui_style(Element::Accent),
+                ),
+                Span::styled(*description, theme.text_style()),
+            ])
+        })
+        .collect();
+    if !errors.is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(errors.iter().map(|error| {
+            Line::from(Span::styled(
+                format!("⚠ {}", error),
+                theme.ratatui_style(Element::Warning),
+            ))
+        }));
+    }
+
+    // Rows, spacing, instructions and borders
+    let width = 64.min(area.width);
+    let height = (lines.len() as u16 + 4).min(area.height);
+    let modal_area = Rect::new(
+        area.x + area.width.saturating_sub(width) / 2,
+        area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    );
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::new()
+        .title(format!(" Keys: {} ", title))
+        .borders(Borders::ALL)
+        .style(theme.ratatui_style(Element::Active));
+    let inner_area = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Bindings
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner_area);
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+
+    let instructions = Paragraph::new("Press any key to close")
+        .alignment(Alignment::Center)
+        .style(theme.ratatui_style(Element::Inactive));
+    frame.render_widget(instructions, chunks[1]);
+}
@@ -0,0 +1,395 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{style::Style, text::Line, text::Span};
+use std::cell::Cell;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Editable text with a cursor, used for the chat line and the settings fields
+#[derive(Debug, Default)]
+pub struct LineEditor {
+    text: String,
+    cursor: usize,       // Byte offset, always on a grapheme boundary
+    scroll: Cell<usize>, // First visible column, kept between frames so the view doesn't jump
+}
+
+impl LineEditor {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Replace the text and put the cursor at the end
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.cursor = self.text.len();
+        self.scroll.set(0);
+    }
+
+    pub fn clear(&mut self) {
+        self.set_text(String::new());
+    }
+
+    /// Number of lines, for sizing the input box
+    pub fn line_count(&self) -> usize {
+        self.text.split('\n').count()
+    }
+
+    /// Insert at the cursor, e.g. a bracketed paste
+    pub fn insert_str(&mut self, text: &str) {
+        self.text.insert_str(self.cursor, text);
+        self.cursor += text.len();
+    }
+
+    pub fn insert_newline(&mut self) {
+        self.insert_str("\n");
+    }
+
+    /// Apply an editing key; returns false for keys the caller should handle
+    ///
+    /// Up on the first line and Down on the last are left to the caller, for
+    /// autocomplete and history.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        match key.code {
+            KeyCode::Char('a') if ctrl => self.cursor = self.line_start(),
+            KeyCode::Char('e') if ctrl => self.cursor = self.line_end(),
+            KeyCode::Char('b') if alt => self.cursor = self.previous_word(),
+            KeyCode::Char('f') if alt => self.cursor = self.next_word(),
+            KeyCode::Char('w') if ctrl => self.delete_back_to(self.previous_word()),
+            KeyCode::Char('u') if ctrl => self.delete_back_to(self.line_start()),
+            KeyCode::Char('k') if ctrl => self.delete_forward_to(self.line_end()),
+            KeyCode::Char('d') if ctrl => self.delete_forward_to(self.next_grapheme()),
+            KeyCode::Char(c) if !ctrl && !alt && !c.is_control() => {
+                let mut buffer = [0; 4];
+                self.insert_str(c.encode_utf8(&mut buffer));
+            }
+            KeyCode::Backspace if ctrl || alt => self.delete_back_to(self.previous_word()),
+            KeyCode::Backspace => self.delete_back_to(self.previous_grapheme()),
+            KeyCode::Delete => self.delete_forward_to(self.next_grapheme()),
+            KeyCode::Left if ctrl || alt => self.cursor = self.previous_word(),
+            KeyCode::Right if ctrl || alt => self.cursor = self.next_word(),
+            KeyCode::Left => self.cursor = self.previous_grapheme(),
+            KeyCode::Right => self.cursor = self.next_grapheme(),
+            KeyCode::Home => self.cursor = self.line_start(),
+            KeyCode::End => self.cursor = self.line_end(),
+            KeyCode::Up if self.line_start() > 0 => self.move_vertically(-1),
+            KeyCode::Down if self.line_end() < self.text.len() => self.move_vertically(1),
+            _ => return false,
+        }
+        true
+    }
+
+    fn previous_grapheme(&self) -> usize {
+        self.text[..self.cursor]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_grapheme(&self) -> usize {
+        self.text[self.cursor..]
+            .graphemes(true)
+            .next()
+            .map_or(self.cursor, |grapheme| self.cursor + grapheme.len())
+    }
+
+    /// Start of the word before the cursor, skipping whitespace first
+    fn previous_word(&self) -> usize {
+        let before = self.text[..self.cursor].trim_end();
+        before.rfind(char::is_whitespace).map_or(0, |index| {
+            index + before[index..].chars().next().map_or(1, char::len_utf8)
+        })
+    }
+
+    /// End of the word after the cursor, skipping whitespace first
+    fn next_word(&self) -> usize {
+        let after = &self.text[self.cursor..];
+        let skipped = after.len() - after.trim_start().len();
+        after[skipped..]
+            .find(char::is_whitespace)
+            .map_or(self.text.len(), |index| self.cursor + skipped + index)
+    }
+
+    fn line_start(&self) -> usize {
+        self.text[..self.cursor]
+            .rfind('\n')
+            .map_or(0, |index| index + 1)
+    }
+
+    fn line_end(&self) -> usize {
+        self.text[self.cursor..]
+            .find('\n')
+            .map_or(self.text.len(), |index| self.cursor + index)
+    }
+
+    fn delete_back_to(&mut self, start: usize) {
+        self.text.replace_range(start..self.cursor, "");
+        self.cursor = start;
+    }
+
+    fn delete_forward_to(&mut self, end: usize) {
+        self.text.replace_range(self.cursor..end, "");
+    }
+
+    /// Move to the same display column on the line above or below, which must exist
+    fn move_vertically(&mut self, step: isize) {
+        let column = self.text[self.line_start()..self.cursor].width();
+        let target_start = if step < 0 {
+            let start = self.line_start();
+            self.text[..start - 1]
+                .rfind('\n')
+                .map_or(0, |index| index + 1)
+        } else {
+            self.line_end() + 1
+        };
+        let line = self.text[target_start..].split('\n').next().unwrap_or("");
+
+        let mut offset = 0;
+        let mut width = 0;
+        for grapheme in line.graphemes(true) {
+            if width + grapheme.width() > column {
+                break;
+            }
+            width += grapheme.width();
+            offset += grapheme.len();
+        }
+        self.cursor = target_start + offset;
+    }
+
+    /// Index of the first line `lines` shows in a box `height` rows tall
+    pub fn first_visible_row(&self, height: u16) -> usize {
+        let cursor_row = self.text[..self.cursor].matches('\n').count();
+        cursor_row.saturating_sub(height.max(1) as usize - 1)
+    }
+
+    /// Row and display column of the cursor
+    fn cursor_position(&self, masked: bool) -> (usize, usize) {
+        let before = &self.text[..self.cursor];
+        let row = before.matches('\n').count();
+        let line = &before[self.line_start()..];
+        let column = if masked {
+            line.graphemes(true).count()
+        } else {
+            line.width()
+        };
+        (row, column)
+    }
+
+    /// The part of the text that fits in `width` x `height`, scrolled to keep
+    /// the cursor in view; `masked` shows every character as a bullet
+    pub fn lines(
+        &self,
+        width: u16,
+        height: u16,
+        style: Style,
+        cursor_style: Style,
+        masked: bool,
+    ) -> Vec<Line<'static>> {
+        let (width, height) = (width as usize, height.max(1) as usize);
+        if width == 0 {
+            return Vec::new();
+        }
+
+        let (cursor_row, cursor_column) = self.cursor_position(masked);
+        let mut scroll = self.scroll.get();
+        if cursor_column < scroll {
+            scroll = cursor_column;
+        } else if cursor_column >= scroll + width {
+            // Leave room for the cursor cell after the last character
+            scroll = cursor_column + 1 - width;
+        }
+        self.scroll.set(scroll);
+        let first_row = self.first_visible_row(height as u16);
+
+        let mut line_start = 0;
+        let mut lines = Vec::new();
+        for (row, line) in self.text.split('\n').enumerate() {
+            if row >= first_row && row < first_row + height {
+                let cursor = (row == cursor_row).then(|| self.cursor - line_start);
+                lines.push(render_line(
+                    line,
+                    cursor,
+                    scroll,
+                    width,
+                    masked,
+                    style,
+                    cursor_style,
+                ));
+            }
+            line_start += line.len() + 1;
+        }
+        lines
+    }
+}
+
+/// One line of text between columns `scroll` and `scroll + width`
+fn render_line(
+    line: &str,
+    cursor: Option<usize>,
+    scroll: usize,
+    width: usize,
+    masked: bool,
+    style: Style,
+    cursor_style: Style,
+) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut column = 0;
+    let end = scroll + width;
+
+    for (offset, grapheme) in line.grapheme_indices(true) {
+        let shown = if masked { "•" } else { grapheme };
+        let grapheme_width = shown.width();
+        let visible_from = column.max(scroll);
+        let visible_to = (column + grapheme_width).min(end);
+        column += grapheme_width;
+        if visible_from >= visible_to {
+            if visible_from >= end {
+                break;
+            }
+            continue;
+        }
+
+        // Wide characters cut by an edge become spaces
+        let text = if visible_to - visible_from < grapheme_width {
+            " ".repeat(visible_to - visible_from)
+        } else {
+            shown.to_string()
+        };
+        if cursor == Some(offset) {
+            spans.push(Span::styled(std::mem::take(&mut plain), style));
+            spans.push(Span::styled(text, cursor_style));
+        } else {
+            plain.push_str(&text);
+        }
+    }
+    spans.push(Span::styled(plain, style));
+
+    if cursor == Some(line.len()) && column < end {
+        spans.push(Span::styled(" ", cursor_style));
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(editor: &mut LineEditor, code: KeyCode) -> bool {
+        editor.handle_key(&KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn rendered(editor: &LineEditor, width: u16, height: u16) -> Vec<String> {
+        editor
+            .lines(width, height, Style::default(), Style::default(), false)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_multibyte_and_wide_characters() {
+        let mut editor = LineEditor::default();
+        editor.set_text("héllo 世界 👍🏽");
+
+        // The skin-tone modifier belongs to the thumbs-up grapheme
+        press(&mut editor, KeyCode::Backspace);
+        assert_eq!(editor.text(), "héllo 世界 ");
+        press(&mut editor, KeyCode::Left);
+        press(&mut editor, KeyCode::Left);
+        press(&mut editor, KeyCode::Backspace);
+        assert_eq!(editor.text(), "héllo 界 ");
+        press(&mut editor, KeyCode::Char('ö'));
+        assert_eq!(editor.text(), "héllo ö界 ");
+
+        editor.set_text("héllo");
+        press(&mut editor, KeyCode::Home);
+        press(&mut editor, KeyCode::Right);
+        press(&mut editor, KeyCode::Delete);
+        assert_eq!(editor.text(), "hllo");
+
+        // Columns count display width: 世 and 界 take two cells each
+        editor.set_text("世界");
+        assert_eq!(editor.cursor_position(false), (0, 4));
+        assert_eq!(editor.cursor_position(true), (0, 2));
+        // A wide character cut by the left edge becomes a space
+        assert_eq!(rendered(&editor, 4, 1), [" 界 "]);
+    }
+
+    #[test]
+    fn test_vertical_movement_across_widths() {
+        let mut editor = LineEditor::default();
+        editor.set_text("abcdef\n世界\nxy");
+        assert_eq!(editor.line_count(), 3);
+
+        // Down from the end of "xy" belongs to the caller
+        assert!(!press(&mut editor, KeyCode::Down));
+
+        // Column 2 on "xy" lands after 世, at column 2 of the wide line
+        assert!(press(&mut editor, KeyCode::Up));
+        assert_eq!(editor.cursor_position(false), (1, 2));
+
+        // Up keeps the display column
+        assert!(press(&mut editor, KeyCode::Up));
+        assert_eq!(editor.cursor_position(false), (0, 2));
+        assert!(!press(&mut editor, KeyCode::Up));
+
+        // Column 5 can't be reached on the wide line, so stop before 界
+        press(&mut editor, KeyCode::End);
+        press(&mut editor, KeyCode::Left);
+        assert!(press(&mut editor, KeyCode::Down));
+        assert_eq!(editor.cursor_position(false), (1, 4));
+
+        // A shorter line clamps to its end
+        assert!(press(&mut editor, KeyCode::Down));
+        assert_eq!(editor.cursor_position(false), (2, 2));
+    }
+
+    #[test]
+    fn test_home_and_end() {
+        let mut editor = LineEditor::default();
+        editor.set_text("first line\nsecond");
+
+        press(&mut editor, KeyCode::Home);
+        assert_eq!(editor.cursor_position(false), (1, 0));
+        press(&mut editor, KeyCode::End);
+        assert_eq!(editor.cursor_position(false), (1, 6));
+
+        // Home and End stay on the cursor's line
+        press(&mut editor, KeyCode::Up);
+        press(&mut editor, KeyCode::End);
+        assert_eq!(editor.cursor_position(false), (0, 10));
+        press(&mut editor, KeyCode::Home);
+        assert_eq!(editor.cursor_position(false), (0, 0));
+
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+        editor.handle_key(&ctrl('e'));
+        assert_eq!(editor.cursor_position(false), (0, 10));
+        editor.handle_key(&ctrl('u'));
+        assert_eq!(editor.text(), "\nsecond");
+    }
+
+    #[test]
+    fn test_scrolling_keeps_cursor_visible() {
+        let mut editor = LineEditor::default();
+        editor.set_text("one\ntwo\nthree");
+        assert_eq!(editor.first_visible_row(2), 1);
+        assert_eq!(rendered(&editor, 10, 2), ["two", "three "]);
+
+        press(&mut editor, KeyCode::Up);
+        press(&mut editor, KeyCode::Up);
+        assert_eq!(editor.first_visible_row(2), 0);
+
+        editor.set_text("abcdefgh");
+        assert_eq!(rendered(&editor, 4, 1), ["fgh "]);
+    }
+}
@@ -0,0 +1,704 @@
+use crate::ui::app::AppMode;
+use agentic_core::settings::{KeymapPreset, KeymapSettings};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A key with its modifiers, written in config as `ctrl+s`, `up`, `?` or `f1`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let (prefix, key) = match text.strip_suffix("++") {
+            // `++` is the plus key itself, `ctrl++` with a modifier
+            Some(prefix) => (Some(prefix).filter(|prefix| !prefix.is_empty()), "+"),
+            None if text == "+" => (None, "+"),
+            None => match text.rsplit_once('+') {
+                Some((prefix, key)) => (Some(prefix), key),
+                None => (None, text),
+            },
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in prefix.into_iter().flat_map(|prefix| prefix.split('+')) {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                other => return Err(format!("unknown modifier '{}' in '{}'", other, text)),
+            };
+        }
+
+        let lowered = key.to_lowercase();
+        let code = match lowered.as_str() {
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "space" => KeyCode::Char(' '),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            function if function.len() > 1 && function.starts_with('f') => function[1..]
+                .parse::<u8>()
+                .ok()
+                .filter(|n| (1..=12).contains(n))
+                .map(KeyCode::F)
+                .ok_or_else(|| format!("unknown key '{}'", key))?,
+            _ => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    // Terminals report CTRL+S as a lowercase `s`
+                    (Some(c), None) if modifiers.contains(KeyModifiers::CONTROL) => {
+                        KeyCode::Char(c.to_ascii_lowercase())
+                    }
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return Err(format!("unknown key '{}'", key)),
+                }
+            }
+        };
+        Ok(Self { code, modifiers })
+    }
+
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let relevant = KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT;
+        let (ours, theirs) = (self.modifiers & relevant, key.modifiers & relevant);
+        match (self.code, key.code) {
+            // Shift is implied by the character itself, e.g. `?` or `S`
+            (KeyCode::Char(a), KeyCode::Char(b)) => {
+                a == b
+                    && ours.difference(KeyModifiers::SHIFT)
+                        == theirs.difference(KeyModifiers::SHIFT)
+            }
+            (a, b) => a == b && ours == theirs,
+        }
+    }
+
+    /// The plain character, for footer hints like `[S]ettings`
+    pub fn char(&self) -> Option<char> {
+        match self.code {
+            KeyCode::Char(c) if self.modifiers.is_empty() => Some(c),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::CONTROL) => {
+                write!(f, "{}", c.to_ascii_uppercase())
+            }
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Delete"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// Something a key can be bound to in one mode
+pub trait Action: Copy + PartialEq + 'static {
+    const ALL: &'static [Self];
+    /// Name used in the `[keymap.<mode>]` config section
+    fn name(self) -> &'static str;
+    fn description(self) -> &'static str;
+}
+
+macro_rules! actions {
+    ($(#[$meta:meta])* $enum:ident { $($variant:ident => $name:literal, $description:literal;)* }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum $enum {
+            $($variant,)*
+        }
+
+        impl Action for $enum {
+            const ALL: &'static [Self] = &[$(Self::$variant,)*];
+
+            fn name(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)*
+                }
+            }
+
+            fn description(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $description,)*
+                }
+            }
+        }
+    };
+}
+
+actions! {
+    /// Main screen
+    NormalAction {
+        Start => "start", "Start chatting (validates settings first)";
+        Settings => "settings", "Open settings";
+        Health => "health", "Check backend health";
+        Theme => "theme", "Pick a theme";
+        About => "about", "About Agentic";
+        Notes => "notes", "Browse saved notes";
+        Quit => "quit", "Quit";
+        Help => "help", "Show key bindings";
+    }
+}
+
+actions! {
+    /// Settings modal
+    SettingsAction {
+        Up => "up", "Previous setting";
+        Down => "down", "Next setting";
+        Previous => "previous", "Previous value of a ◄ ► setting";
+        Next => "next", "Next value of a ◄ ► setting";
+        Edit => "edit", "Edit or pick the selected setting";
+        DropFallback => "drop_fallback", "Drop the last fallback cloud model";
+        Save => "save", "Save and close";
+        Close => "close", "Close settings";
+        Help => "help", "Show key bindings";
+    }
+}
+
+actions! {
+    /// Choosing one of the orchestrator's proposals
+    ProposalAction {
+        Previous => "previous", "Previous proposal";
+        Next => "next", "Next proposal";
+        Synthesize => "synthesize", "Synthesize the selected proposal";
+        Copy => "copy", "Copy the selected proposal to the clipboard";
+        Cancel => "cancel", "Cancel and return to the main screen";
+        Help => "help", "Show key bindings";
+    }
+}
+
+actions! {
+    /// Reviewing a synthesized note
+    NoteAction {
+        Save => "save", "Save the note and ask another question";
+        Discard => "discard", "Discard the note and ask another question";
+        ScrollUp => "scroll_up", "Scroll up";
+        ScrollDown => "scroll_down", "Scroll down";
+        PageUp => "page_up", "Scroll up a page";
+        PageDown => "page_down", "Scroll down a page";
+        Top => "top", "Jump to the start";
+        Bottom => "bottom", "Jump to the end";
+        Copy => "copy", "Copy the note text to the clipboard";
+        CopyMarkdown => "copy_markdown", "Copy the note as saved, with front matter";
+        CopyTags => "copy_tags", "Copy the tags";
+        Edit => "edit", "Save the note and open it in your editor";
+        Close => "close", "Return to the main screen without saving";
+        Help => "help", "Show key bindings";
+    }
+}
+
+actions! {
+    /// The `/status` health panel
+    StatusAction {
+        Recheck => "recheck", "Check the backends again";
+        Close => "close", "Close the panel";
+        Help => "help", "Show key bindings";
+    }
+}
+
+actions! {
+    /// The theme picker
+    ThemeAction {
+        Previous => "previous", "Preview the previous theme";
+        Next => "next", "Preview the next theme";
+        Apply => "apply", "Keep the highlighted theme";
+        Cancel => "cancel", "Restore the previous theme";
+        Help => "help", "Show key bindings";
+    }
+}
+
+actions! {
+    /// The saved notes list
+    NotesAction {
+        Previous => "previous", "Previous note";
+        Next => "next", "Next note";
+        Open => "open", "Open the note in your editor";
+        Close => "close", "Close the list";
+        Help => "help", "Show key bindings";
+    }
+}
+
+actions! {
+    /// About and error messages
+    AboutAction {
+        ScrollUp => "scroll_up", "Scroll up";
+        ScrollDown => "scroll_down", "Scroll down";
+        PageUp => "page_up", "Scroll up a page";
+        PageDown => "page_down", "Scroll down a page";
+        Top => "top", "Jump to the start";
+        Bottom => "bottom", "Jump to the end";
+        Close => "close", "Close";
+        Help => "help", "Show key bindings";
+    }
+}
+
+/// The keys bound to each action of one mode
+#[derive(Debug, Clone)]
+pub struct Bindings<A> {
+    entries: Vec<(A, Vec<KeyChord>)>,
+}
+
+impl<A: Action> Bindings<A> {
+    fn new(defaults: &[(A, &[&str])]) -> Self {
+        let entries = A::ALL
+            .iter()
+            .map(|&action| {
+                let keys = defaults
+                    .iter()
+                    .filter(|(bound, _)| *bound == action)
+                    .flat_map(|(_, keys)| keys.iter())
+                    .filter_map(|key| KeyChord::parse(key).ok())
+                    .collect();
+                (action, keys)
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// The action bound to `key`, if any
+    pub fn action(&self, key: &KeyEvent) -> Option<A> {
+        self.entries
+            .iter()
+            .find(|(_, keys)| keys.iter().any(|chord| chord.matches(key)))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn keys(&self, action: A) -> &[KeyChord] {
+        self.entries
+            .iter()
+            .find(|(bound, _)| *bound == action)
+            .map_or(&[], |(_, keys)| keys.as_slice())
+    }
+
+    /// First key of an action, for hints
+    pub fn label(&self, action: A) -> String {
+        self.keys(action)
+            .first()
+            .map_or_else(|| "unbound".to_string(), KeyChord::to_string)
+    }
+
+    /// Replace the keys of the actions named in `overrides`
+    ///
+    /// A rebound key is taken away from any other action in the same mode.
+    fn apply(&mut self, mode: &str, overrides: &BTreeMap<String, Vec<String>>) -> Vec<String> {
+        let mut errors = Vec::new();
+        for (name, keys) in overrides {
+            let Some(action) = A::ALL.iter().copied().find(|action| action.name() == name) else {
+                errors.push(format!("[keymap.{}] has no action '{}'", mode, name));
+                continue;
+            };
+            let mut chords = Vec::new();
+            for key in keys {
+                match KeyChord::parse(key) {
+                    Ok(chord) => chords.push(chord),
+                    Err(e) => errors.push(format!("[keymap.{}] {}: {}", mode, name, e)),
+                }
+            }
+            for (_, bound) in &mut self.entries {
+                bound.retain(|chord| !chords.contains(chord));
+            }
+            if let Some((_, bound)) = self.entries.iter_mut().find(|(a, _)| *a == action) {
+                *bound = chords;
+            }
+        }
+        errors
+    }
+
+    /// (keys, description) rows for the help overlay
+    fn help(&self) -> Vec<(String, &'static str)> {
+        self.entries
+            .iter()
+            .filter(|(_, keys)| !keys.is_empty())
+            .map(|(action, keys)| {
+                let keys: Vec<String> = keys.iter().map(KeyChord::to_string).collect();
+                (keys.join(" / "), action.description())
+            })
+            .collect()
+    }
+}
+
+/// Every remappable binding, built from a preset and the config overrides
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    pub normal: Bindings<NormalAction>,
+    pub settings: Bindings<SettingsAction>,
+    pub proposals: Bindings<ProposalAction>,
+    pub note: Bindings<NoteAction>,
+    pub notes: Bindings<NotesAction>,
+    pub status: Bindings<StatusAction>,
+    pub theme: Bindings<ThemeAction>,
+    pub about: Bindings<AboutAction>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::preset(KeymapPreset::Default)
+    }
+}
+
+impl Keymap {
+    pub fn preset(preset: KeymapPreset) -> Self {
+        let vim = preset == KeymapPreset::Vim;
+        // Vim keys are added alongside the arrows rather than replacing them
+        let pick = |default: &'static [&'static str], vim_keys: &'static [&'static str]| {
+            if vim {
+                vim_keys
+            } else {
+                default
+            }
+        };
+
+        Self {
+            normal: Bindings::new(&[
+                (NormalAction::Start, pick(&["enter"], &["enter", "i"])),
+                (NormalAction::Settings, &["s"]),
+                (NormalAction::Health, &["h"]),
+                (NormalAction::Theme, &["t"]),
+                (NormalAction::About, &["a"]),
+                (NormalAction::Notes, &["n"]),
+                (NormalAction::Quit, pick(&["q"], &["q", "Z"])),
+                (NormalAction::Help, &["?", "f1"]),
+            ]),
+            settings: Bindings::new(&[
+                (SettingsAction::Up, pick(&["up"], &["up", "k"])),
+                (SettingsAction::Down, pick(&["down"], &["down", "j"])),
+                (SettingsAction::Previous, pick(&["left"], &["left", "h"])),
+                (SettingsAction::Next, pick(&["right"], &["right", "l"])),
+                (SettingsAction::Edit, &["enter"]),
+                (SettingsAction::DropFallback, &["backspace"]),
+                (SettingsAction::Save, pick(&["s"], &["s", "w"])),
+                (SettingsAction::Close, pick(&["esc"], &["esc", "q"])),
+                (SettingsAction::Help, &["?", "f1"]),
+            ]),
+            proposals: Bindings::new(&[
+                (ProposalAction::Previous, pick(&["up"], &["up", "k"])),
+                (ProposalAction::Next, pick(&["down"], &["down", "j"])),
+                (ProposalAction::Synthesize, &["enter"]),
+                (ProposalAction::Copy, pick(&["c"], &["y"])),
+                (ProposalAction::Cancel, pick(&["esc"], &["esc", "q"])),
+                (ProposalAction::Help, &["?", "f1"]),
+            ]),
+            note: Bindings::new(&[
+                (NoteAction::Save, pick(&["s"], &["w"])),
+                (NoteAction::Discard, pick(&["d"], &["d"])),
+                (NoteAction::ScrollUp, pick(&["up", "left"], &["up", "k"])),
+                (
+                    NoteAction::ScrollDown,
+                    pick(&["down", "right"], &["down", "j"]),
+                ),
+                (NoteAction::PageUp, pick(&["pageup"], &["pageup", "ctrl+b"])),
+                (
+                    NoteAction::PageDown,
+                    pick(&["pagedown", "space"], &["pagedown", "ctrl+f"]),
+                ),
+                (NoteAction::Top, pick(&["home"], &["home", "g"])),
+                (NoteAction::Bottom, pick(&["end"], &["end", "G"])),
+                (NoteAction::Copy, pick(&["c"], &["y"])),
+                (NoteAction::CopyMarkdown, pick(&["m"], &["Y"])),
+                (NoteAction::CopyTags, &["t"]),
+                (NoteAction::Edit, &["e"]),
+                (NoteAction::Close, pick(&["esc", "enter"], &["esc", "q"])),
+                (NoteAction::Help, &["?", "f1"]),
+            ]),
+            notes: Bindings::new(&[
+                (NotesAction::Previous, pick(&["up"], &["up", "k"])),
+                (NotesAction::Next, pick(&["down"], &["down", "j"])),
+                (NotesAction::Open, pick(&["enter", "e"], &["enter", "e"])),
+                (NotesAction::Close, pick(&["esc"], &["esc", "q"])),
+                (NotesAction::Help, &["?", "f1"]),
+            ]),
+            status: Bindings::new(&[
+                (StatusAction::Recheck, &["r"]),
+                (
+                    StatusAction::Close,
+                    pick(&["esc", "enter"], &["esc", "enter", "q"]),
+                ),
+                (StatusAction::Help, &["?", "f1"]),
+            ]),
+            theme: Bindings::new(&[
+                (ThemeAction::Previous, pick(&["up"], &["up", "k"])),
+                (ThemeAction::Next, pick(&["down"], &["down", "j"])),
+                (ThemeAction::Apply, &["enter"]),
+                (ThemeAction::Cancel, pick(&["esc"], &["esc", "q"])),
+                (ThemeAction::Help, &["?", "f1"]),
+            ]),
+            about: Bindings::new(&[
+                (AboutAction::ScrollUp, pick(&["up", "left"], &["up", "k"])),
+                (
+                    AboutAction::ScrollDown,
+                    pick(&["down", "right"], &["down", "j"]),
+                ),
+                (
+                    AboutAction::PageUp,
+                    pick(&["pageup"], &["pageup", "ctrl+b"]),
+                ),
+                (
+                    AboutAction::PageDown,
+                    pick(&["pagedown", "space"], &["pagedown", "ctrl+f"]),
+                ),
+                (AboutAction::Top, pick(&["home"], &["home", "g"])),
+                (AboutAction::Bottom, pick(&["end"], &["end", "G"])),
+                (
+                    AboutAction::Close,
+                    pick(&["esc", "enter"], &["esc", "enter", "q"]),
+                ),
+                (AboutAction::Help, &["?", "f1"]),
+            ]),
+        }
+    }
+
+    /// The preset with the config overrides applied, plus any problems found
+    pub fn from_settings(settings: &KeymapSettings) -> (Self, Vec<String>) {
+        let mut keymap = Self::preset(settings.preset);
+        let mut errors = Vec::new();
+        for (mode, overrides) in &settings.overrides {
+            errors.extend(match mode.as_str() {
+                "normal" => keymap.normal.apply(mode, overrides),
+                "settings" => keymap.settings.apply(mode, overrides),
+                "proposals" => keymap.proposals.apply(mode, overrides),
+                "note" => keymap.note.apply(mode, overrides),
+                "notes" => keymap.notes.apply(mode, overrides),
+                "status" => keymap.status.apply(mode, overrides),
+                "theme" => keymap.theme.apply(mode, overrides),
+                "about" => keymap.about.apply(mode, overrides),
+                _ => vec![format!("[keymap.{}] is not a mode", mode)],
+            });
+        }
+        (keymap, errors)
+    }
+
+    /// Title and (keys, description) rows of the bindings active in `mode`
+    pub fn help(&self, mode: AppMode) -> (&'static str, Vec<(String, &'static str)>) {
+        let fixed = |rows: &[(&str, &'static str)]| {
+            rows.iter()
+                .map(|(keys, description)| (keys.to_string(), *description))
+                .collect()
+        };
+        match mode {
+            AppMode::Normal => ("Main screen", self.normal.help()),
+            AppMode::Settings => ("Settings", self.settings.help()),
+            AppMode::Orchestrating => ("Proposals", self.proposals.help()),
+            AppMode::Complete => ("Note", self.note.help()),
+            AppMode::Notes => ("Saved notes", self.notes.help()),
+            AppMode::Status => ("Status", self.status.help()),
+            AppMode::SelectingTheme => ("Theme picker", self.theme.help()),
+            AppMode::CoachingTip => ("Message", self.about.help()),
+            // Text entry keeps fixed keys so every letter can be typed
+            AppMode::Chat => (
+                "Chat",
+                fixed(&[
+                    ("Enter", "Send the query or run the /command"),
+                    ("Alt+Enter", "Start a new line"),
+                    ("← / → / Home / End", "Move the cursor"),
+                    ("Ctrl+← / Ctrl+→", "Move by word"),
+                    (
+                        "Ctrl+W / Ctrl+U / Ctrl+K",
+                        "Delete the word, to line start or to line end",
+                    ),
+                    ("Tab", "Complete the /command"),
+                    ("↑ / ↓", "Choose a /command, or recall earlier queries"),
+                    ("Ctrl+R", "Search earlier queries (again for older matches)"),
+                    ("Esc", "Cancel a running request and leave the chat"),
+                    ("F1", "Show key bindings"),
+                ]),
+            ),
+            AppMode::EditingEndpoint | AppMode::EditingApiKey | AppMode::EditingGeneration => (
+                "Editing",
+                fixed(&[
+                    ("Enter", "Save the value"),
+                    ("← / → / Home / End", "Move the cursor"),
+                    (
+                        "Ctrl+W / Ctrl+U",
+                        "Delete the word or the whole line before the cursor",
+                    ),
+                    ("Esc", "Cancel the edit"),
+                    ("F1", "Show key bindings"),
+                ]),
+            ),
+            AppMode::SelectingLocalModel | AppMode::SelectingCloudModel => (
+                "Model picker",
+                fixed(&[
+                    ("Type", "Fuzzy-search the models"),
+                    ("↑ / ↓ / ← / →", "Move through the list and pages"),
+                    ("Enter", "Pick the model"),
+                    ("Tab", "Change the sort order"),
+                    ("Ctrl+A", "Add as a fallback cloud model"),
+                    ("Ctrl+F", "Show only free cloud models"),
+                    ("Ctrl+R", "Refresh the cloud catalogue"),
+                    (
+                        "Ctrl+P / Ctrl+D / Ctrl+O",
+                        "Pull, delete or inspect an Ollama model",
+                    ),
+                    ("Esc", "Clear the search, then close"),
+                    ("F1", "Show key bindings"),
+                ]),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(text: &str) -> KeyChord {
+        KeyChord::parse(text).unwrap()
+    }
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    fn overrides(mode: &str, action: &str, keys: &[&str]) -> KeymapSettings {
+        let keys = keys.iter().map(|key| key.to_string()).collect();
+        KeymapSettings {
+            overrides: BTreeMap::from([(
+                mode.to_string(),
+                BTreeMap::from([(action.to_string(), keys)]),
+            )]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            chord("ctrl+s"),
+            KeyChord {
+                code: KeyCode::Char('s'),
+                modifiers: KeyModifiers::CONTROL,
+            }
+        );
+        // Terminals report Ctrl with a lowercase letter
+        assert_eq!(chord("Ctrl+S"), chord("ctrl+s"));
+        assert_eq!(chord("?").code, KeyCode::Char('?'));
+        assert_eq!(chord("G").code, KeyCode::Char('G'));
+        assert_eq!(chord("++"), chord("+"));
+        assert_eq!(chord("++").code, KeyCode::Char('+'));
+        assert_eq!(chord("ctrl++").modifiers, KeyModifiers::CONTROL);
+        assert_eq!(
+            chord("alt+shift+up").modifiers,
+            KeyModifiers::ALT | KeyModifiers::SHIFT
+        );
+        assert_eq!(chord("f1").code, KeyCode::F(1));
+        assert_eq!(chord("F12").code, KeyCode::F(12));
+        assert_eq!(chord("space").code, KeyCode::Char(' '));
+
+        assert!(KeyChord::parse("f13").is_err());
+        assert!(KeyChord::parse("pgup").is_err());
+        assert_eq!(
+            KeyChord::parse("hyper+x"),
+            Err("unknown modifier 'hyper' in 'hyper+x'".to_string())
+        );
+
+        assert_eq!(chord("ctrl+s").to_string(), "Ctrl+S");
+        assert_eq!(chord("pagedown").to_string(), "PgDn");
+    }
+
+    #[test]
+    fn test_matches() {
+        // Shift comes with the character, whether or not the terminal says so
+        for modifiers in [KeyModifiers::NONE, KeyModifiers::SHIFT] {
+            assert!(chord("?").matches(&key(KeyCode::Char('?'), modifiers)));
+            assert!(chord("G").matches(&key(KeyCode::Char('G'), modifiers)));
+        }
+        assert!(!chord("G").matches(&key(KeyCode::Char('g'), KeyModifiers::NONE)));
+
+        assert!(chord("ctrl+s").matches(&key(KeyCode::Char('s'), KeyModifiers::CONTROL)));
+        assert!(!chord("ctrl+s").matches(&key(KeyCode::Char('s'), KeyModifiers::NONE)));
+        assert!(!chord("s").matches(&key(KeyCode::Char('s'), KeyModifiers::CONTROL)));
+
+        // Other keys need their modifiers exactly
+        assert!(chord("up").matches(&key(KeyCode::Up, KeyModifiers::NONE)));
+        assert!(!chord("up").matches(&key(KeyCode::Up, KeyModifiers::SHIFT)));
+    }
+
+    #[test]
+    fn test_overrides() {
+        let press = |c| key(KeyCode::Char(c), KeyModifiers::NONE);
+
+        // Taking `s` for quit leaves settings without it
+        let (keymap, errors) = Keymap::from_settings(&overrides("normal", "quit", &["s", "x"]));
+        assert!(errors.is_empty());
+        assert_eq!(keymap.normal.action(&press('s')), Some(NormalAction::Quit));
+        assert_eq!(keymap.normal.action(&press('x')), Some(NormalAction::Quit));
+        assert_eq!(keymap.normal.action(&press('q')), None);
+        assert!(keymap.normal.keys(NormalAction::Settings).is_empty());
+        assert_eq!(keymap.normal.label(NormalAction::Settings), "unbound");
+        // Other modes keep their own `s`
+        assert_eq!(
+            keymap.settings.action(&press('s')),
+            Some(SettingsAction::Save)
+        );
+
+        let (_, errors) = Keymap::from_settings(&overrides("normal", "launch", &["l"]));
+        assert_eq!(errors, ["[keymap.normal] has no action 'launch'"]);
+        let (_, errors) = Keymap::from_settings(&overrides("chat", "send", &["enter"]));
+        assert_eq!(errors, ["[keymap.chat] is not a mode"]);
+
+        // A bad key is reported and the good ones still bound
+        let (keymap, errors) =
+            Keymap::from_settings(&overrides("status", "recheck", &["f13", "ctrl+r"]));
+        assert_eq!(errors, ["[keymap.status] recheck: unknown key 'f13'"]);
+        assert_eq!(keymap.status.label(StatusAction::Recheck), "Ctrl+R");
+    }
+
+    #[test]
+    fn test_vim_preset() {
+        let press = |c| key(KeyCode::Char(c), KeyModifiers::NONE);
+        let vim = Keymap::preset(KeymapPreset::Vim);
+        let default = Keymap::default();
+
+        assert_eq!(vim.settings.action(&press('j')), Some(SettingsAction::Down));
+        assert_eq!(default.settings.action(&press('j')), None);
+        // Arrows keep working alongside the vim keys
+        let up = key(KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(vim.notes.action(&up), Some(NotesAction::Previous));
+        assert_eq!(vim.note.action(&press('G')), Some(NoteAction::Bottom));
+        assert_eq!(vim.note.action(&press('w')), Some(NoteAction::Save));
+        assert_eq!(vim.note.label(NoteAction::Save), "w");
+        assert_eq!(default.note.label(NoteAction::Save), "s");
+        assert_eq!(
+            vim.note
+                .action(&key(KeyCode::Char('f'), KeyModifiers::CONTROL)),
+            Some(NoteAction::PageDown)
+        );
+    }
+}
@@ -0,0 +1,563 @@
+use agentic_core::theme::{Element, Theme};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
+};
+
+/// Styled lines for the Markdown that models tend to write: headings, lists,
+/// quotes, fenced code, rules, inline emphasis, code spans, links and `$math$`
+pub fn render_markdown(source: &str, theme: &Theme) -> Text<'static> {
+    let code_style = theme.ratatui_style(Element::Info);
+    let mut lines = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut in_math = false;
+
+    for raw in source.lines() {
+        let trimmed = raw.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            } else {
+                lines.push(Line::from(Span::styled(format!("  {}", raw), code_style)));
+            }
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker))
+        {
+            fence = Some(marker);
+            continue;
+        }
+        if trimmed.trim_end() == "$$" {
+            in_math = !in_math;
+            continue;
+        }
+        if in_math {
+            lines.push(Line::from(Span::styled(
+                format!("    {}", latex_to_unicode(trimmed)),
+                code_style.add_modifier(Modifier::ITALIC),
+            )));
+            continue;
+        }
+        lines.push(block_line(raw, theme));
+    }
+    Text::from(lines)
+}
+
+/// One line outside code blocks
+fn block_line(raw: &str, theme: &Theme) -> Line<'static> {
+    let trimmed = raw.trim_start();
+    let indent = "  ".repeat((raw.len() - trimmed.len()) / 2);
+    let text_style = theme.ratatui_style(Element::Text);
+    let marker_style = theme.ratatui_style(Element::Accent);
+
+    let hashes = trimmed.chars().take_while(|&c| c == '#').count();
+    if (1..=6).contains(&hashes) && trimmed[hashes..].starts_with(' ') {
+        let mut style = theme
+            .ratatui_style(Element::Title)
+            .add_modifier(Modifier::BOLD);
+        if hashes == 1 {
+            style = style.add_modifier(Modifier::UNDERLINED);
+        }
+        return Line::from(inline_spans(trimmed[hashes..].trim(), style, theme));
+    }
+
+    let compact: String = trimmed.chars().filter(|c| !c.is_whitespace()).collect();
+    if compact.len() >= 3
+        && ['-', '*', '_']
+            .into_iter()
+            .any(|rule| compact.chars().all(|c| c == rule))
+    {
+        return Line::from(Span::styled(
+            "─".repeat(24),
+            theme.ratatui_style(Element::Border),
+        ));
+    }
+
+    if let Some(quote) = trimmed.strip_prefix('>') {
+        let style = theme
+            .ratatui_style(Element::Inactive)
+            .add_modifier(Modifier::ITALIC);
+        let mut spans = vec![Span::styled(format!("{}│ ", indent), style)];
+        spans.extend(inline_spans(quote.trim_start(), style, theme));
+        return Line::from(spans);
+    }
+
+    let bullet = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| trimmed.strip_prefix(marker));
+    if let Some(item) = bullet {
+        let (marker, item) = if let Some(rest) = item.strip_prefix("[ ] ") {
+            ("☐ ", rest)
+        } else if let Some(rest) = item
+            .strip_prefix("[x] ")
+            .or_else(|| item.strip_prefix("[X] "))
+        {
+            ("☑ ", rest)
+        } else {
+            ("• ", item)
+        };
+        let mut spans = vec![Span::styled(format!("{}{}", indent, marker), marker_style)];
+        spans.extend(inline_spans(item, text_style, theme));
+        return Line::from(spans);
+    }
+
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 && (trimmed[digits..].starts_with(". ") || trimmed[digits..].starts_with(") ")) {
+        let mut spans = vec![Span::styled(
+            format!("{}{}. ", indent, &trimmed[..digits]),
+            marker_style,
+        )];
+        spans.extend(inline_spans(&trimmed[digits + 2..], text_style, theme));
+        return Line::from(spans);
+    }
+
+    let mut spans = vec![Span::raw(indent)];
+    spans.extend(inline_spans(trimmed, text_style, theme));
+    Line::from(spans)
+}
+
+/// Inline Markdown on top of `base`
+///
+/// Delimiters only count when they can open or close, so `2 * 3`, `snake_case`
+/// and `$5 to $10` stay as written, and an opener without a closer is literal.
+pub fn inline_spans(text: &str, base: Style, theme: &Theme) -> Vec<Span<'static>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let (mut bold, mut italic, mut strike) = (false, false, false);
+    let current = |bold: bool, italic: bool, strike: bool| {
+        let mut style = base;
+        if bold {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+        if italic {
+            style = style.add_modifier(Modifier::ITALIC);
+        }
+        if strike {
+            style = style.add_modifier(Modifier::CROSSED_OUT);
+        }
+        style
+    };
+    let flush = |plain: &mut String, spans: &mut Vec<Span<'static>>, style: Style| {
+        if !plain.is_empty() {
+            spans.push(Span::styled(std::mem::take(plain), style));
+        }
+    };
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let previous = i.checked_sub(1).map(|p| chars[p]);
+        match c {
+            '\\' if chars
+                .get(i + 1)
+                .is_some_and(|next| next.is_ascii_punctuation()) =>
+            {
+                plain.push(chars[i + 1]);
+                i += 2;
+                continue;
+            }
+            '`' => {
+                if let Some(end) = find_char(&chars, i + 1, '`') {
+                    flush(&mut plain, &mut spans, current(bold, italic, strike));
+                    let code: String = chars[i + 1..end].iter().collect();
+                    spans.push(Span::styled(code, theme.ratatui_style(Element::Info)));
+                    i = end + 1;
+                    continue;
+                }
+            }
+            '$' if chars.get(i + 1).is_some_and(|next| !next.is_whitespace()) => {
+                let close = (i + 2..chars.len()).find(|&end| {
+                    chars[end] == '$'
+                        && !chars[end - 1].is_whitespace()
+                        && !chars.get(end + 1).is_some_and(char::is_ascii_digit)
+                });
+                if let Some(end) = close {
+                    flush(&mut plain, &mut spans, current(bold, italic, strike));
+                    let math: String = chars[i + 1..end].iter().collect();
+                    spans.push(Span::styled(
+                        latex_to_unicode(&math),
+                        theme
+                            .ratatui_style(Element::Info)
+                            .add_modifier(Modifier::ITALIC),
+                    ));
+                    i = end + 1;
+                    continue;
+                }
+            }
+            '[' => {
+                let label_end = find_char(&chars, i + 1, ']');
+                let link = label_end
+                    .filter(|&end| chars.get(end + 1) == Some(&'('))
+                    .and_then(|end| find_char(&chars, end + 2, ')').map(|close| (end, close)));
+                if let Some((end, close)) = link {
+                    flush(&mut plain, &mut spans, current(bold, italic, strike));
+                    let label: String = chars[i + 1..end].iter().collect();
+                    let url: String = chars[end + 2..close].iter().collect();
+                    spans.push(Span::styled(
+                        label.clone(),
+                        theme
+                            .ratatui_style(Element::Accent)
+                            .add_modifier(Modifier::UNDERLINED),
+                    ));
+                    if url != label {
+                        spans.push(Span::styled(
+                            format!(" ({})", url),
+                            theme.ratatui_style(Element::Inactive),
+                        ));
+                    }
+                    i = close + 1;
+                    continue;
+                }
+            }
+            '*' | '_' | '~' => {
+                let run = chars[i..].iter().take_while(|&&d| d == c).count();
+                let next = chars.get(i + run).copied();
+                let can_open = next.is_some_and(|n| !n.is_whitespace())
+                    && (c != '_' || !previous.is_some_and(char::is_alphanumeric));
+                let can_close = previous.is_some_and(|p| !p.is_whitespace())
+                    && (c != '_' || !next.is_some_and(char::is_alphanumeric));
+                let delimiter: String = std::iter::repeat_n(c, run.min(3)).collect();
+                let has_closer = chars[i + run..]
+                    .iter()
+                    .collect::<String>()
+                    .contains(&delimiter);
+
+                // `~~` strikes through; `*`/`_` italic, doubled bold, tripled both
+                let (toggle_bold, toggle_italic, toggle_strike) = match (c, run) {
+                    ('~', 2) => (false, false, true),
+                    ('~', _) => (false, false, false),
+                    (_, 1) => (false, true, false),
+                    (_, 2) => (true, false, false),
+                    (_, 3) => (true, true, false),
+                    _ => (false, false, false),
+                };
+                let active =
+                    (toggle_bold && bold) || (toggle_italic && italic) || (toggle_strike && strike);
+                let applies = (toggle_bold || toggle_italic || toggle_strike)
+                    && ((active && can_close) || (!active && can_open && has_closer));
+                if applies {
+                    flush(&mut plain, &mut spans, current(bold, italic, strike));
+                    bold ^= toggle_bold;
+                    italic ^= toggle_italic;
+                    strike ^= toggle_strike;
+                    i += run;
+                    continue;
+                }
+                plain.extend(std::iter::repeat_n(c, run));
+                i += run;
+                continue;
+            }
+            _ => {}
+        }
+        plain.push(c);
+        i += 1;
+    }
+    flush(&mut plain, &mut spans, current(bold, italic, strike));
+    spans
+}
+
+fn find_char(chars: &[char], from: usize, target: char) -> Option<usize> {
+    (from..chars.len()).find(|&index| chars[index] == target)
+}
+
+const LATEX_SYMBOLS: &[(&str, &str)] = &[
+    ("alpha", "α"),
+    ("beta", "β"),
+    ("gamma", "γ"),
+    ("delta", "δ"),
+    ("epsilon", "ε"),
+    ("theta", "θ"),
+    ("lambda", "λ"),
+    ("mu", "μ"),
+    ("pi", "π"),
+    ("rho", "ρ"),
+    ("sigma", "σ"),
+    ("tau", "τ"),
+    ("phi", "φ"),
+    ("omega", "ω"),
+    ("Delta", "Δ"),
+    ("Sigma", "Σ"),
+    ("Omega", "Ω"),
+    ("sum", "∑"),
+    ("prod", "∏"),
+    ("int", "∫"),
+    ("infty", "∞"),
+    ("partial", "∂"),
+    ("nabla", "∇"),
+    ("times", "×"),
+    ("cdot", "·"),
+    ("pm", "±"),
+    ("leq", "≤"),
+    ("le", "≤"),
+    ("geq", "≥"),
+    ("ge", "≥"),
+    ("neq", "≠"),
+    ("approx", "≈"),
+    ("equiv", "≡"),
+    ("to", "→"),
+    ("rightarrow", "→"),
+    ("leftarrow", "←"),
+    ("Rightarrow", "⇒"),
+    ("in", "∈"),
+    ("forall", "∀"),
+    ("exists", "∃"),
+];
+
+const SUPERSCRIPTS: &str = "⁰¹²³⁴⁵⁶⁷⁸⁹";
+const SUBSCRIPTS: &str = "₀₁₂₃₄₅₆₇₈₉";
+
+/// Readable plain text for common LaTeX: Greek letters, operators,
+/// `\frac{a}{b}`, `\sqrt{x}` and numeric super- and subscripts
+fn latex_to_unicode(source: &str) -> String {
+    let chars: Vec<char> = source.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => {
+                let name: String = chars[i + 1..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_alphabetic())
+                    .collect();
+                i += 1 + name.len();
+                match name.as_str() {
+                    "frac" => {
+                        let (numerator, next) = brace_group(&chars, i);
+                        let (denominator, next) = brace_group(&chars, next);
+                        out.push_str(&format!(
+                            "{}/{}",
+                            latex_to_unicode(&numerator),
+                            latex_to_unicode(&denominator)
+                        ));
+                        i = next;
+                    }
+                    "sqrt" => {
+                        let (radicand, next) = brace_group(&chars, i);
+                        out.push_str(&format!("√({})", latex_to_unicode(&radicand)));
+                        i = next;
+                    }
+                    "" => {
+                        // `\,`, `\{` and friends
+                        if let Some(&escaped) = chars.get(i) {
+                            if !matches!(escaped, ',' | ';' | '!') {
+                                out.push(escaped);
+                            }
+                            i += 1;
+                        }
+                    }
+                    _ => match LATEX_SYMBOLS.iter().find(|(command, _)| *command == name) {
+                        Some((_, symbol)) => out.push_str(symbol),
+                        // `\text{...}`, `\mathbf{...}`: keep only the argument
+                        None if chars.get(i) == Some(&'{') => {}
+                        None => out.push_str(&name),
+                    },
+                }
+            }
+            marker @ ('^' | '_') => {
+                let (group, next) = if chars.get(i + 1) == Some(&'{') {
+                    brace_group(&chars, i + 1)
+                } else {
+                    (
+                        chars.get(i + 1).map(|c| c.to_string()).unwrap_or_default(),
+                        i + 2,
+                    )
+                };
+                let table: Vec<char> = if marker == '^' {
+                    SUPERSCRIPTS.chars().collect()
+                } else {
+                    SUBSCRIPTS.chars().collect()
+                };
+                let mapped: Option<String> = group
+                    .chars()
+                    .map(|c| c.to_digit(10).map(|digit| table[digit as usize]))
+                    .collect();
+                match mapped.filter(|mapped| !mapped.is_empty()) {
+                    Some(mapped) => out.push_str(&mapped),
+                    None => {
+                        out.push(marker);
+                        out.push_str(&latex_to_unicode(&group));
+                    }
+                }
+                i = next.min(chars.len());
+            }
+            '{' | '}' => i += 1,
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Contents of the `{...}` group starting at `start`, and the index after it
+fn brace_group(chars: &[char], start: usize) -> (String, usize) {
+    if chars.get(start) != Some(&'{') {
+        return (String::new(), start);
+    }
+    let mut depth = 0;
+    for (index, &c) in chars.iter().enumerate().skip(start) {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return (chars[start + 1..index].iter().collect(), index + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    (chars[start + 1..].iter().collect(), chars.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(line: &Line) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    /// Text and modifiers of each span, empty ones left out
+    fn styled(spans: &[Span]) -> Vec<(String, Modifier)> {
+        spans
+            .iter()
+            .filter(|span| !span.content.is_empty())
+            .map(|span| (span.content.to_string(), span.style.add_modifier))
+            .collect()
+    }
+
+    fn inline(text: &str) -> Vec<(String, Modifier)> {
+        styled(&inline_spans(text, Style::default(), &Theme::default()))
+    }
+
+    #[test]
+    fn test_emphasis() {
+        assert_eq!(
+            inline("a *b* **c** ***d*** ~~e~~"),
+            [
+                ("a ".to_string(), Modifier::empty()),
+                ("b".to_string(), Modifier::ITALIC),
+                (" ".to_string(), Modifier::empty()),
+                ("c".to_string(), Modifier::BOLD),
+                (" ".to_string(), Modifier::empty()),
+                ("d".to_string(), Modifier::BOLD | Modifier::ITALIC),
+                (" ".to_string(), Modifier::empty()),
+                ("e".to_string(), Modifier::CROSSED_OUT),
+            ]
+        );
+        assert_eq!(
+            inline("__bold__ and _it_"),
+            [
+                ("bold".to_string(), Modifier::BOLD),
+                (" and ".to_string(), Modifier::empty()),
+                ("it".to_string(), Modifier::ITALIC),
+            ]
+        );
+
+        let theme = Theme::default();
+        let spans = inline_spans(
+            "see `x * y` and [docs](https://example.com)",
+            Style::default(),
+            &theme,
+        );
+        let text: String = spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(text, "see x * y and docs (https://example.com)");
+        assert_eq!(spans[1].style, theme.ratatui_style(Element::Info));
+    }
+
+    #[test]
+    fn test_unterminated_markers_stay_literal() {
+        for text in [
+            "2 * 3 = 6",
+            "snake_case_name",
+            "**not closed",
+            "a ~~half",
+            "`open code",
+            "[label](no close",
+            "costs $5 to $10",
+            "escaped \\*stars\\*",
+        ] {
+            let expected = text.replace('\\', "");
+            assert_eq!(inline(text), [(expected, Modifier::empty())], "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_lists_and_blocks() {
+        let theme = Theme::default();
+        let text = render_markdown(
+            "# Title\n- item\n  * nested\n- [ ] todo\n- [x] done\n3. third\n> quoted\n---",
+            &theme,
+        );
+        let lines: Vec<String> = text.lines.iter().map(plain).collect();
+        assert_eq!(
+            lines,
+            [
+                "Title",
+                "• item",
+                "  • nested",
+                "☐ todo",
+                "☑ done",
+                "3. third",
+                "│ quoted",
+                &"─".repeat(24),
+            ]
+        );
+        let title = text.lines[0].spans[0].style.add_modifier;
+        assert!(title.contains(Modifier::BOLD | Modifier::UNDERLINED));
+    }
+
+    #[test]
+    fn test_fenced_code() {
+        let theme = Theme::default();
+        let text = render_markdown(
+            "before\n```rust\nlet x = *y*;\n```\n~~~\n# not a heading\n~~~\nafter",
+            &theme,
+        );
+        let lines: Vec<String> = text.lines.iter().map(plain).collect();
+        assert_eq!(
+            lines,
+            ["before", "  let x = *y*;", "  # not a heading", "after"]
+        );
+        // Code keeps its markers and gets one style
+        assert_eq!(text.lines[1].spans.len(), 1);
+        assert_eq!(
+            text.lines[1].spans[0].style,
+            theme.ratatui_style(Element::Info)
+        );
+
+        // An unclosed fence runs to the end
+        let text = render_markdown("```\n**raw**", &theme);
+        assert_eq!(plain(&text.lines[0]), "  **raw**");
+    }
+
+    #[test]
+    fn test_math() {
+        assert_eq!(latex_to_unicode(r"\alpha + \beta \leq \pi"), "α + β ≤ π");
+        assert_eq!(latex_to_unicode(r"x^2 + y_{10}"), "x² + y₁₀");
+        assert_eq!(latex_to_unicode(r"\frac{a}{b} = \sqrt{c}"), "a/b = √(c)");
+        assert_eq!(latex_to_unicode(r"e^{i\pi}"), "e^iπ");
+        assert_eq!(latex_to_unicode(r"\text{speed}\,v"), "speedv");
+
+        assert_eq!(
+            inline("energy $E = mc^2$ here"),
+            [
+                ("energy ".to_string(), Modifier::empty()),
+                ("E = mc²".to_string(), Modifier::ITALIC),
+                (" here".to_string(), Modifier::empty()),
+            ]
+        );
+        let theme = Theme::default();
+        let text = render_markdown("$$\n\\sum x_1\n$$", &theme);
+        assert_eq!(text.lines.len(), 1);
+        assert_eq!(plain(&text.lines[0]), "    ∑ x₁");
+    }
+}
@@ -1,6 +1,15 @@
 pub mod app;
 pub mod chat;
+pub mod clipboard;
 pub mod footer;
 pub mod header;
+pub mod help_overlay;
+pub mod input;
+pub mod keymap;
+pub mod markdown;
 pub mod model_selection_modal;
+pub mod notes_picker;
+pub mod scroll;
 pub mod settings_modal;
+pub mod status_panel;
+pub mod theme_picker;
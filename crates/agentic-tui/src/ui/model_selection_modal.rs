@@ -1,18 +1,30 @@
-use agentic_core::theme::{Element, Theme};
+use agentic_core::{
+    model_filter::ModelFilter,
+    theme::{Element, Theme},
+};
 use ratatui::{
     prelude::{Alignment, Constraint, Direction, Frame, Layout, Rect},
     style::Modifier,
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, LineGauge, List, ListItem, Paragraph, Wrap},
 };
 
 pub struct ModelSelectionParams<'a> {
     pub theme: &'a Theme,
     pub title: &'a str,
-    pub models: &'a [(String, String)], // (name, info)
+    pub models: &'a [(String, String)], // (name, info) after filtering
+    pub total_models: usize,            // Before filtering
+    pub loading: bool,                  // The list hasn't arrived yet
+    pub filter: &'a ModelFilter,
+    pub show_free_toggle: bool,
+    pub status: Option<String>, // e.g. how old the cached catalogue is
+    pub details: &'a [(String, String)], // (label, value) for the selected model
     pub selected_index: usize,
     pub current_page: usize,
     pub models_per_page: usize,
+    pub allow_fallback: bool, // Offer adding the model to the fallback chain
+    pub progress: Option<(String, Option<f64>)>, // Running download: label and ratio
+    pub manage_models: bool,  // Offer pulling, deleting and inspecting
 }
 
 pub fn render_model_selection_modal(frame: &mut Frame, area: Rect, params: ModelSelectionParams) {
@@ -24,8 +36,8 @@ pub fn render_model_selection_modal(frame: &mut Frame, area: Rect, params: Model
     let inner_area = block.inner(area);
     frame.render_widget(block, area);
 
-    if params.models.is_empty() {
-        // Show loading or error message
+    if params.loading {
+        // Show loading message
         let loading_text = "Loading available models...";
         let loading_paragraph = Paragraph::new(loading_text)
             .alignment(Alignment::Center)
@@ -38,79 +50,187 @@ pub fn render_model_selection_modal(frame: &mut Frame, area: Rect, params: Model
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
+            Constraint::Length(1), // Search line
             Constraint::Length(1), // Page indicator
-            Constraint::Min(0),    // Model list
-            Constraint::Length(3), // Instructions
+            Constraint::Min(0),    // Model list and details
+            Constraint::Length(2), // Instructions
         ])
         .split(inner_area);
 
+    // Search line with the active sort and toggles
+    let mut search_spans = vec![
+        Span::styled("Search: ", params.theme.ratatui_style(Element::Inactive)),
+        Span::styled(
+            format!("{}_", params.filter.query),
+            params.theme.text_style().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("  sort: {}", params.filter.sort.label()),
+            params.theme.ratatui_style(Element::Inactive),
+        ),
+    ];
+    if params.show_free_toggle && params.filter.free_only {
+        search_spans.push(Span::styled(
+            "  🆓 free only",
+            params.theme.ratatui_style(Element::Info),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(search_spans)), chunks[0]);
+
     // Calculate pagination
-    let total_pages = params.models.len().div_ceil(params.models_per_page);
+    let total_pages = params.models.len().div_ceil(params.models_per_page).max(1);
     let start_index = params.current_page * params.models_per_page;
     let end_index = std::cmp::min(start_index + params.models_per_page, params.models.len());
 
     // Page indicator
-    let page_info = if total_pages > 1 {
+    let mut page_info = if total_pages > 1 {
         format!(
-            "Page {} of {} ({} models)",
+            "Page {} of {} ({} of {} models)",
             params.current_page + 1,
             total_pages,
-            params.models.len()
+            params.models.len(),
+            params.total_models
         )
     } else {
-        format!("{} models", params.models.len())
+        format!("{} of {} models", params.models.len(), params.total_models)
     };
-    let page_paragraph = Paragraph::new(page_info)
-        .alignment(Alignment::Center)
-        .style(params.theme.ratatui_style(Element::Inactive));
-    frame.render_widget(page_paragraph, chunks[0]);
+    if let Some(status) = &params.status {
+        page_info.push_str(&format!(" · {}", status));
+    }
+    if let Some((label, ratio)) = &params.progress {
+        // A running pull takes over the page line
+        let gauge = LineGauge::default()
+            .label(label.as_str())
+            .ratio(ratio.unwrap_or(0.0))
+            .style(params.theme.ratatui_style(Element::Inactive))
+            .gauge_style(params.theme.ratatui_style(Element::Info));
+        frame.render_widget(gauge, chunks[1]);
+    } else {
+        let page_paragraph = Paragraph::new(page_info)
+            .alignment(Alignment::Center)
+            .style(params.theme.ratatui_style(Element::Inactive));
+        frame.render_widget(page_paragraph, chunks[1]);
+    }
 
-    // Get models for current page
-    let page_models = &params.models[start_index..end_index];
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(chunks[2]);
 
-    // Create list items for current page
-    let items: Vec<ListItem> = page_models
+    if params.models.is_empty() {
+        // An empty server still needs the search line to pull a first model
+        let empty_text = if params.total_models > 0 {
+            format!("No models match '{}'", params.filter.query)
+        } else if params.manage_models {
+            "No models installed, type a name and press CTRL+P".to_string()
+        } else {
+            "No models available".to_string()
+        };
+        let empty_paragraph = Paragraph::new(empty_text)
+            .wrap(Wrap { trim: true })
+            .alignment(Alignment::Center)
+            .style(params.theme.ratatui_style(Element::Inactive));
+        frame.render_widget(empty_paragraph, body[0]);
+    } else {
+        // Get models for current page
+        let page_models = &params.models[start_index..end_index];
+        let name_width = (body[0].width as usize).saturating_sub(12);
+
+        // Create list items for current page
+        let items: Vec<ListItem> = page_models
+            .iter()
+            .enumerate()
+            .map(|(i, (name, info))| {
+                let global_index = start_index + i;
+                let style = if global_index == params.selected_index {
+                    params.theme.highlight_style()
+                } else {
+                    params.theme.text_style()
+                };
+
+                let line = if info.is_empty() {
+                    // Just show the name when there's no secondary info
+                    Line::from(Span::styled(
+                        name.clone(),
+                        style.add_modifier(Modifier::BOLD),
+                    ))
+                } else {
+                    // Show name and info in columns when both are present
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{:<width$}", truncate(name, name_width), width = name_width),
+                            style.add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(format!("{:>10}", info), style),
+                    ])
+                };
+
+                ListItem::new(line)
+            })
+            .collect();
+
+        let list = List::new(items).style(params.theme.ratatui_style(Element::Text));
+        frame.render_widget(list, body[0]);
+    }
+
+    // Details pane for the selected model
+    let detail_lines: Vec<Line> = params
+        .details
         .iter()
-        .enumerate()
-        .map(|(i, (name, info))| {
-            let global_index = start_index + i;
-            let style = if global_index == params.selected_index {
-                params.theme.highlight_style()
-            } else {
-                params.theme.text_style()
-            };
-
-            let line = if info.is_empty() {
-                // Just show the name when there's no secondary info
-                Line::from(Span::styled(
-                    name.clone(),
-                    style.add_modifier(Modifier::BOLD),
-                ))
-            } else {
-                // Show name and info in columns when both are present
-                Line::from(vec![
-                    Span::styled(format!("{:<40}", name), style.add_modifier(Modifier::BOLD)),
-                    Span::styled(format!("{:>15}", info), style),
-                ])
-            };
-
-            ListItem::new(line)
+        .map(|(label, value)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{}: ", label),
+                    params.theme.ratatui_style(Element::Inactive),
+                ),
+                Span::styled(value.clone(), params.theme.text_style()),
+            ])
         })
         .collect();
-
-    let list = List::new(items).style(params.theme.ratatui_style(Element::Text));
-
-    frame.render_widget(list, chunks[1]);
+    let details_paragraph = Paragraph::new(detail_lines)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::new()
+                .borders(Borders::LEFT)
+                .title(" Details ")
+                .style(params.theme.ratatui_style(Element::Inactive)),
+        );
+    frame.render_widget(details_paragraph, body[1]);
 
     // Instructions
-    let instructions = if total_pages > 1 {
-        "[↑↓] Navigate | [←→] Page | [ENTER] Select | [ESC] Cancel"
+    let paging = if total_pages > 1 {
+        " | [←→] Page"
+    } else {
+        ""
+    };
+    let free_toggle = if params.show_free_toggle {
+        " | [CTRL+F] Free only | [CTRL+R] Refresh"
     } else {
-        "[↑↓] Navigate | [ENTER] Select | [ESC] Cancel"
+        ""
     };
+    let fallback = if params.allow_fallback {
+        " | [CTRL+A] Add fallback"
+    } else if params.manage_models {
+        " | [CTRL+P] Pull typed name | [CTRL+D] Delete | [CTRL+O] Inspect"
+    } else {
+        ""
+    };
+    let instructions = format!(
+        "Type to search | [↑↓] Navigate{} | [TAB] Sort{}\n[ENTER] Select{} | [ESC] Clear/Cancel",
+        paging, free_toggle, fallback
+    );
     let instructions_paragraph = Paragraph::new(instructions)
         .alignment(Alignment::Center)
         .style(params.theme.ratatui_style(Element::Inactive));
 
-    frame.render_widget(instructions_paragraph, chunks[2]);
+    frame.render_widget(instructions_paragraph, chunks[3]);
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        let kept: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+        format!("{}…", kept)
+    }
 }
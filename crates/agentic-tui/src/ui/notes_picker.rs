@@ -0,0 +1,126 @@
+use crate::ui::keymap::{Bindings, NotesAction};
+use crate::ui::markdown::render_markdown;
+use agentic_core::models::AtomicNote;
+use agentic_core::notes::SavedNote;
+use agentic_core::theme::{Element, Theme};
+use chrono::{DateTime, Local};
+use ratatui::{
+    prelude::{Alignment, Constraint, Direction, Frame, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+use std::path::Path;
+
+/// Saved notes, newest first, with the selected one highlighted and its body
+/// previewed below the list
+#[allow(clippy::too_many_arguments)]
+pub fn render_notes_picker(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    notes: &[SavedNote],
+    selected: usize,
+    preview: Option<&AtomicNote>,
+    dir: &Path,
+    keys: &Bindings<NotesAction>,
+) {
+    // Notes, spacing, instructions and borders, within 80% of the screen; a
+    // preview takes all of it
+    let width = 72.min(area.width);
+    let height = if preview.is_some() {
+        area.height * 4 / 5
+    } else {
+        (notes.len().max(1) as u16 + 4).min(area.height * 4 / 5)
+    };
+    let modal_area = Rect::new(
+        area.x + area.width.saturating_sub(width) / 2,
+        area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    );
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::new()
+        .title(format!(" Saved notes ({}) ", notes.len()))
+        .borders(Borders::ALL)
+        .style(theme.ratatui_style(Element::Active));
+    let inner_area = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    // The list gets up to a third of the modal when there is a preview
+    let list_rows = match preview {
+        Some(_) => (notes.len() as u16).clamp(1, (inner_area.height / 3).max(1)),
+        None => inner_area.height,
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Max(list_rows), // Notes
+            Constraint::Min(0),         // Preview
+            Constraint::Length(1),      // Spacing
+            Constraint::Length(1),      // Instructions
+        ])
+        .split(inner_area);
+
+    let lines: Vec<Line> = if notes.is_empty() {
+        vec![Line::from(Span::styled(
+            format!("No notes saved in {} yet", dir.display()),
+            theme.ratatui_style(Element::Inactive),
+        ))]
+    } else {
+        // Keep the selection on screen in long lists
+        let rows = usize::from(chunks[0].height.max(1));
+        let first = selected.saturating_sub(rows - 1);
+        notes
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(rows)
+            .map(|(index, note)| {
+                let date = DateTime::<Local>::from(note.modified).format("%Y-%m-%d %H:%M");
+                if index == selected {
+                    Line::from(Span::styled(
+                        format!("▶ {}  {}", date, note.title),
+                        theme.highlight_style(),
+                    ))
+                } else {
+                    Line::from(vec![
+                        Span::styled(
+                            format!("  {}  ", date),
+                            theme.ratatui_style(Element::Inactive),
+                        ),
+                        Span::styled(note.title.clone(), theme.text_style()),
+                    ])
+                }
+            })
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    if let Some(note) = preview {
+        let preview_block = Block::new()
+            .title(" Preview ")
+            .borders(Borders::TOP)
+            .style(theme.ratatui_style(Element::Inactive));
+        let preview_area = preview_block.inner(chunks[1]);
+        frame.render_widget(preview_block, chunks[1]);
+        frame.render_widget(
+            Paragraph::new(render_markdown(note.body_text.trim(), theme))
+                .style(theme.ratatui_style(Element::Text))
+                .wrap(Wrap { trim: false }),
+            preview_area,
+        );
+    }
+
+    let instructions = Paragraph::new(format!(
+        "[{}{}] Select | [{}] Edit | [{}] Close | [{}] Help",
+        keys.label(NotesAction::Previous),
+        keys.label(NotesAction::Next),
+        keys.label(NotesAction::Open),
+        keys.label(NotesAction::Close),
+        keys.label(NotesAction::Help),
+    ))
+    .alignment(Alignment::Center)
+    .style(theme.ratatui_style(Element::Inactive));
+    frame.render_widget(instructions, chunks[3]);
+}
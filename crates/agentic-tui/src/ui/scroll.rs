@@ -0,0 +1,156 @@
+use agentic_core::theme::{Element, Theme};
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    prelude::{Frame, Rect},
+    widgets::{Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+};
+use std::cell::Cell;
+
+/// Rows moved per mouse wheel notch
+const WHEEL_STEP: i32 = 3;
+
+/// Scroll position of a wrapped text view
+///
+/// How tall wrapped text is depends on the area it is drawn in, so every frame
+/// records the measurements and the key and mouse handlers scroll within them.
+#[derive(Debug, Default)]
+pub struct ScrollView {
+    offset: Cell<u16>,
+    max: Cell<u16>,
+    area: Cell<Rect>,  // Where the text was drawn
+    track: Cell<Rect>, // Where the scrollbar was drawn, empty when it wasn't
+}
+
+impl ScrollView {
+    pub fn offset(&self) -> u16 {
+        self.offset.get()
+    }
+
+    /// Where the text was last drawn, for mapping clicks to content rows
+    pub fn area(&self) -> Rect {
+        self.area.get()
+    }
+
+    pub fn reset(&self) {
+        self.offset.set(0);
+    }
+
+    pub fn scroll_by(&self, rows: i32) {
+        let offset = (i32::from(self.offset.get()) + rows).clamp(0, i32::from(self.max.get()));
+        self.offset.set(offset as u16);
+    }
+
+    /// A page minus one row, so the last line read stays on screen
+    pub fn page_up(&self) {
+        self.scroll_by(-self.page_step());
+    }
+
+    pub fn page_down(&self) {
+        self.scroll_by(self.page_step());
+    }
+
+    pub fn top(&self) {
+        self.offset.set(0);
+    }
+
+    pub fn bottom(&self) {
+        self.offset.set(self.max.get());
+    }
+
+    fn page_step(&self) -> i32 {
+        i32::from(self.area.get().height.saturating_sub(1).max(1))
+    }
+
+    /// Record the text area and the wrapped height drawn in it
+    pub fn measure(&self, area: Rect, content_height: usize) {
+        let content_height = content_height.min(u16::MAX as usize) as u16;
+        self.area.set(area);
+        self.max.set(content_height.saturating_sub(area.height));
+        self.offset.set(self.offset.get().min(self.max.get()));
+    }
+
+    /// Scroll as little as possible to show content rows `start..end`
+    pub fn reveal(&self, start: u16, end: u16) {
+        let height = self.area.get().height;
+        let mut offset = self.offset.get();
+        if end > offset + height {
+            offset = end.saturating_sub(height);
+        }
+        offset = offset.min(start).min(self.max.get());
+        self.offset.set(offset);
+    }
+
+    /// Draw a wrapping paragraph into `area` at the current offset
+    pub fn render(&self, frame: &mut Frame, area: Rect, paragraph: Paragraph) {
+        self.measure(area, paragraph.line_count(area.width));
+        frame.render_widget(paragraph.scroll((self.offset.get(), 0)), area);
+    }
+
+    /// Draw a scrollbar in the last column of `track`, usually a block's right
+    /// border; nothing is drawn when the text fits
+    pub fn render_scrollbar(&self, frame: &mut Frame, track: Rect, theme: &Theme) {
+        let max = self.max.get();
+        if max == 0 || track.width == 0 || track.height == 0 {
+            self.track.set(Rect::default());
+            return;
+        }
+        let track = Rect::new(track.right() - 1, track.y, 1, track.height);
+        self.track.set(track);
+
+        let mut state = ScrollbarState::new(usize::from(max) + 1)
+            .position(usize::from(self.offset.get()))
+            .viewport_content_length(usize::from(self.area.get().height));
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(None)
+            .end_symbol(None)
+            .track_style(theme.ratatui_style(Element::Border))
+            .thumb_style(theme.ratatui_style(Element::Accent));
+        frame.render_stateful_widget(scrollbar, track, &mut state);
+    }
+
+    /// Wheel over the text and clicks or drags on the scrollbar; returns false
+    /// for events it doesn't use
+    pub fn handle_mouse(&self, event: &MouseEvent) -> bool {
+        let track = self.track.get();
+        match event.kind {
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+                if contains(self.area.get(), event) || contains(track, event) =>
+            {
+                let step = if event.kind == MouseEventKind::ScrollUp {
+                    -WHEEL_STEP
+                } else {
+                    WHEEL_STEP
+                };
+                self.scroll_by(step);
+            }
+            MouseEventKind::Down(MouseButton::Left) if contains(track, event) => {
+                self.jump_to(event.row);
+            }
+            // Dragging keeps following the pointer once it leaves the column
+            MouseEventKind::Drag(MouseButton::Left)
+                if track.height > 0 && event.row >= track.y && event.row < track.bottom() =>
+            {
+                self.jump_to(event.row);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Put the offset where `row` falls along the scrollbar
+    fn jump_to(&self, row: u16) {
+        let track = self.track.get();
+        let span = u32::from(track.height.saturating_sub(1).max(1));
+        let position = u32::from(row.saturating_sub(track.y).min(track.height - 1));
+        let offset = (position * u32::from(self.max.get()) + span / 2) / span;
+        self.offset.set(offset as u16);
+    }
+}
+
+/// Whether the pointer is inside `area`
+pub fn contains(area: Rect, event: &MouseEvent) -> bool {
+    event.column >= area.x
+        && event.column < area.right()
+        && event.row >= area.y
+        && event.row < area.bottom()
+}
@@ -1,14 +1,18 @@
 use crate::ui::app::{AppMode, SettingsSelection};
+use crate::ui::input::LineEditor;
+use crate::ui::keymap::{Bindings, SettingsAction};
 use agentic_core::{
-    settings::Settings,
-    theme::{Element, Theme, ThemeVariant},
+    settings::{ProviderSetting, RunMode, Settings},
+    theme::{Element, Theme},
 };
 use ratatui::{
     prelude::{Alignment, Constraint, Direction, Frame, Layout, Rect},
+    style::Modifier,
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn render_settings_modal(
     frame: &mut Frame,
     area: Rect,
@@ -16,7 +20,9 @@ pub fn render_settings_modal(
     theme: &Theme,
     selection: SettingsSelection,
     mode: AppMode,
-    edit_buffer: &str,
+    edit_buffer: &LineEditor,
+    notice: Option<&str>,
+    keys: &Bindings<SettingsAction>,
 ) {
     let block = Block::new()
         .title("Settings")
@@ -31,9 +37,13 @@ pub fn render_settings_modal(
         .margin(1)
         .constraints([
             Constraint::Length(1), // Endpoint
+            Constraint::Length(1), // Provider
             Constraint::Length(1), // Local Model
             Constraint::Length(1), // API Key
             Constraint::Length(1), // Cloud Model
+            Constraint::Length(1), // Orchestrator
+            Constraint::Length(1), // Synthesizer
+            Constraint::Length(1), // Run Mode
             Constraint::Length(1), // Theme
             Constraint::Min(0),    // Spacer
             Constraint::Length(1), // Action Text
@@ -41,12 +51,8 @@ pub fn render_settings_modal(
         .split(inner_area);
 
     // Helper to create a setting line
-    let create_setting_line = |label: &str, value: &str, is_selected: bool, is_editing: bool| {
-        let display_value = if is_editing {
-            format!("{}_", value) // Add cursor indicator when editing
-        } else {
-            value.to_owned()
-        };
+    let create_setting_line = |label: &str, value: &str, is_selected: bool| {
+        let display_value = value.to_owned();
 
         if is_selected {
             // Selected: highlight background + bright text (full focus treatment)
@@ -66,20 +72,63 @@ pub fn render_settings_modal(
         }
     };
 
+    // The row being edited shows the line editor and its cursor
+    let value_width = inner_area.width.saturating_sub(2 + 15);
+    let create_editing_line = |label: &str, placeholder: &str, masked: bool| {
+        let cursor_style = theme.highlight_style().add_modifier(Modifier::REVERSED);
+        let mut spans = vec![Span::styled(
+            format!("{:<15}", label),
+            theme.highlight_style(),
+        )];
+        if edit_buffer.is_empty() {
+            spans.push(Span::styled(" ", cursor_style));
+            spans.push(Span::styled(
+                placeholder.to_owned(),
+                theme.highlight_style(),
+            ));
+        } else if let Some(line) = edit_buffer
+            .lines(
+                value_width,
+                1,
+                theme.highlight_style(),
+                cursor_style,
+                masked,
+            )
+            .pop()
+        {
+            spans.extend(line.spans);
+        }
+        Line::from(spans)
+    };
+
     // Endpoint
-    let endpoint_value = if matches!(mode, AppMode::EditingEndpoint) {
-        edit_buffer
+    let endpoint_line = if matches!(mode, AppMode::EditingEndpoint) {
+        create_editing_line("Endpoint:", "", false)
     } else {
-        &settings.endpoint
+        create_setting_line(
+            "Endpoint:",
+            &settings.endpoint,
+            selection == SettingsSelection::Endpoint,
+        )
     };
-    let endpoint_line = create_setting_line(
-        "Endpoint:",
-        endpoint_value,
-        selection == SettingsSelection::Endpoint,
-        matches!(mode, AppMode::EditingEndpoint),
-    );
     frame.render_widget(Paragraph::new(endpoint_line), chunks[0]);
 
+    // Provider
+    let provider_value = match settings.provider {
+        ProviderSetting::Auto => "◄ AUTO ►",
+        ProviderSetting::Ollama => "◄ OLLAMA ►",
+        ProviderSetting::LmStudio => "◄ LM STUDIO ►",
+        ProviderSetting::OpenAiCompatible => "◄ OPENAI-COMPATIBLE ►",
+        ProviderSetting::LlamaCpp => "◄ LLAMA.CPP ►",
+        ProviderSetting::Mock => "◄ MOCK ►",
+    };
+    let provider_line = create_setting_line(
+        "Provider:",
+        provider_value,
+        selection == SettingsSelection::Provider,
+    );
+    frame.render_widget(Paragraph::new(provider_line), chunks[1]);
+
     // Local Model
     let local_model_value = if settings.local_model == "[SELECT]" {
         "[SELECT MODEL FROM OLLAMA]"
@@ -90,62 +139,111 @@ pub fn render_settings_modal(
         "Local Model:",
         local_model_value,
         selection == SettingsSelection::LocalModel,
-        false,
     );
-    frame.render_widget(Paragraph::new(local_model_line), chunks[1]);
+    frame.render_widget(Paragraph::new(local_model_line), chunks[2]);
 
-    // API Key - always show truncated display
-    let api_key_display = if matches!(mode, AppMode::EditingApiKey) {
-        if edit_buffer.is_empty() {
-            "[PASTE YOUR KEY HERE]".to_string()
-        } else {
-            format_api_key_display(edit_buffer)
-        }
+    // API Key - truncated when shown, masked while typed
+    let api_key_line = if matches!(mode, AppMode::EditingApiKey) {
+        create_editing_line("API Key:", "[PASTE YOUR KEY HERE]", true)
     } else {
-        format_api_key_display(&settings.api_key)
+        create_setting_line(
+            "API Key:",
+            &format_api_key_display(&settings.api_key),
+            selection == SettingsSelection::ApiKey,
+        )
     };
-    let api_key_line = create_setting_line(
-        "API Key:",
-        &api_key_display,
-        selection == SettingsSelection::ApiKey,
-        matches!(mode, AppMode::EditingApiKey),
-    );
-    frame.render_widget(Paragraph::new(api_key_line), chunks[2]);
+    frame.render_widget(Paragraph::new(api_key_line), chunks[3]);
 
     // Cloud Model
     let cloud_model_value = if settings.cloud_model == "[SELECT]" {
-        "[SELECT FROM OPENROUTER :FREE]"
+        "[SELECT FROM OPENROUTER :FREE]".to_string()
     } else {
-        &settings.cloud_model
+        settings.cloud_model.to_string()
     };
     let cloud_model_line = create_setting_line(
         "Cloud Model:",
-        cloud_model_value,
+        &cloud_model_value,
         selection == SettingsSelection::CloudModel,
-        false,
     );
-    frame.render_widget(Paragraph::new(cloud_model_line), chunks[3]);
+    frame.render_widget(Paragraph::new(cloud_model_line), chunks[4]);
 
-    // Theme
-    let theme_value = match settings.theme {
-        ThemeVariant::EverforestDark => "◄ DARK ►",
-        ThemeVariant::EverforestLight => "◄ LIGHT ►",
+    // Generation parameters per role, edited as key=value pairs
+    let editing_generation = matches!(mode, AppMode::EditingGeneration);
+    let orchestrator_line = if editing_generation && selection == SettingsSelection::Orchestrator {
+        create_editing_line("Orchestrator:", "", false)
+    } else {
+        create_setting_line(
+            "Orchestrator:",
+            &settings.orchestrator.to_compact_string(),
+            selection == SettingsSelection::Orchestrator,
+        )
+    };
+    frame.render_widget(Paragraph::new(orchestrator_line), chunks[5]);
+
+    let synthesizer_line = if editing_generation && selection == SettingsSelection::Synthesizer {
+        create_editing_line("Synthesizer:", "", false)
+    } else {
+        create_setting_line(
+            "Synthesizer:",
+            &settings.synthesizer.to_compact_string(),
+            selection == SettingsSelection::Synthesizer,
+        )
+    };
+    frame.render_widget(Paragraph::new(synthesizer_line), chunks[6]);
+
+    // Run Mode
+    let run_mode_value = match settings.run_mode {
+        RunMode::Hybrid => "◄ HYBRID ►",
+        RunMode::LocalOnly => "◄ LOCAL ONLY ►",
+        RunMode::CloudOnly => "◄ CLOUD ONLY ►",
     };
+    let run_mode_line = create_setting_line(
+        "Mode:",
+        run_mode_value,
+        selection == SettingsSelection::RunMode,
+    );
+    frame.render_widget(Paragraph::new(run_mode_line), chunks[7]);
+
+    // Theme
+    let theme_value = format!("◄ {} ►", settings.theme.label().to_uppercase());
     let theme_line = create_setting_line(
         "Theme:",
-        theme_value,
+        &theme_value,
         selection == SettingsSelection::Theme,
-        false,
     );
-    frame.render_widget(Paragraph::new(theme_line), chunks[4]);
+    frame.render_widget(Paragraph::new(theme_line), chunks[8]);
 
-    // Action Text
+    // Action Text; text fields keep fixed keys, the rest follow the keymap
     let action_text = match mode {
-        AppMode::EditingApiKey => "[ENTER] Save | [CTRL+V] Paste | [ESC] Cancel",
-        AppMode::EditingEndpoint => "[ENTER] Save | [ESC] Cancel",
-        _ => "[↑↓] Navigate | [S]ave changes | [ESC] Return",
+        AppMode::EditingApiKey => {
+            "[ENTER] Save | Paste replaces the key | [ESC] Cancel".to_string()
+        }
+        AppMode::EditingEndpoint => "[ENTER] Save | [ESC] Cancel".to_string(),
+        AppMode::EditingGeneration => notice
+            .unwrap_or("key=value, '-' unsets | [ENTER] Save | [ESC] Cancel")
+            .to_string(),
+        _ if selection == SettingsSelection::CloudModel
+            && !settings.cloud_model.fallbacks().is_empty() =>
+        {
+            format!(
+                "[{}] Pick | [{}] Drop fallback | [{}] Save | [{}] Return",
+                keys.label(SettingsAction::Edit),
+                keys.label(SettingsAction::DropFallback),
+                keys.label(SettingsAction::Save),
+                keys.label(SettingsAction::Close),
+            )
+        }
+        _ => format!(
+            "[{}{}] Navigate | [{}] Save changes | [{}] Return",
+            keys.label(SettingsAction::Up),
+            keys.label(SettingsAction::Down),
+            keys.label(SettingsAction::Save),
+            keys.label(SettingsAction::Close),
+        ),
     };
-    let action_style = if selection == SettingsSelection::Save {
+    let action_style = if notice.is_some() {
+        theme.warning_style()
+    } else if selection == SettingsSelection::Save {
         theme.highlight_style()
     } else {
         theme.ratatui_style(Element::Inactive)
@@ -153,7 +251,7 @@ pub fn render_settings_modal(
     let action_paragraph = Paragraph::new(action_text)
         .alignment(Alignment::Center)
         .style(action_style);
-    frame.render_widget(action_paragraph, chunks[6]);
+    frame.render_widget(action_paragraph, chunks[10]);
 }
 
 fn format_api_key_display(api_key: &str) -> String {
@@ -0,0 +1,166 @@
+use crate::ui::keymap::{Bindings, StatusAction};
+use agentic_core::{
+    health::BackendHealth,
+    models::KeyInfo,
+    theme::{Element, Theme},
+};
+use ratatui::{
+    prelude::{Alignment, Constraint, Direction, Frame, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use std::time::Duration;
+
+/// What the panel knows about one backend
+pub enum BackendStatus<'a> {
+    Checking,
+    Skipped(&'a str), // Not used in the current run mode
+    Checked(&'a BackendHealth),
+}
+
+pub fn render_status_panel(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    local: BackendStatus,
+    cloud: BackendStatus,
+    keys: &Bindings<StatusAction>,
+) {
+    let block = Block::new()
+        .title(" Status ")
+        .borders(Borders::ALL)
+        .style(theme.ratatui_style(Element::Active));
+
+    let inner_area = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Backends
+            Constraint::Length(1), // Instructions
+        ])
+        .split(inner_area);
+
+    let mut lines = backend_lines(theme, "LOCAL", &local);
+    lines.push(Line::from(""));
+    lines.extend(backend_lines(theme, "CLOUD", &cloud));
+
+    let body = Paragraph::new(lines).wrap(Wrap { trim: false });
+    frame.render_widget(body, chunks[0]);
+
+    let footer = Paragraph::new(format!(
+        "[{}] Re-check | [{}] Return",
+        keys.label(StatusAction::Recheck),
+        keys.label(StatusAction::Close),
+    ))
+    .alignment(Alignment::Center)
+    .style(theme.ratatui_style(Element::Inactive));
+    frame.render_widget(footer, chunks[1]);
+}
+
+fn backend_lines<'a>(theme: &Theme, name: &'a str, status: &BackendStatus) -> Vec<Line<'a>> {
+    let heading = |summary: String, element: Element| {
+        Line::from(vec![
+            Span::styled(
+                format!("{:<7}", name),
+                theme.text_style().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(summary, theme.ratatui_style(element)),
+        ])
+    };
+
+    let health = match status {
+        BackendStatus::Checking => return vec![heading("🔄 checking...".into(), Element::Info)],
+        BackendStatus::Skipped(reason) => {
+            return vec![heading(format!("– {}", reason), Element::Inactive)]
+        }
+        BackendStatus::Checked(health) => health,
+    };
+
+    let (state, element) = if health.healthy() {
+        ("● ready", Element::Accent)
+    } else if health.reachable() {
+        ("● degraded", Element::Warning)
+    } else {
+        ("● unreachable", Element::Warning)
+    };
+    let mut summary = format!("{}  {}", state, health.target);
+    if let Some(provider) = &health.provider {
+        summary.push_str(&format!(" ({:?})", provider));
+    }
+    if let Some(latency) = health.latency {
+        summary.push_str(&format!("  {}", format_latency(latency)));
+    }
+
+    let mut lines = vec![heading(summary, element)];
+
+    let mut model_line = format!("model {}", health.model);
+    match health.model_loaded {
+        Some(true) => model_line.push_str(" · loaded in memory"),
+        Some(false) => model_line.push_str(" · not loaded (first request will be slow)"),
+        None => {}
+    }
+    lines.push(detail_line(theme, model_line));
+
+    if let Some(key) = &health.key_info {
+        lines.push(detail_line(theme, format_key_info(key)));
+    }
+
+    for step in &health.steps {
+        let (mark, style) = if step.passed() {
+            ("✅", theme.text_style())
+        } else {
+            ("❌", theme.warning_style())
+        };
+        let mut text = format!(
+            "{} {:<32} {:>7}",
+            mark,
+            step.label,
+            format_latency(step.elapsed)
+        );
+        if let Some(error) = &step.error {
+            text.push_str(&format!("  {}", error));
+        }
+        lines.push(Line::from(Span::styled(format!("       {}", text), style)));
+    }
+
+    lines
+}
+
+fn detail_line<'a>(theme: &Theme, text: String) -> Line<'a> {
+    Line::from(Span::styled(
+        format!("       {}", text),
+        theme.ratatui_style(Element::Inactive),
+    ))
+}
+
+fn format_key_info(key: &KeyInfo) -> String {
+    let mut parts = Vec::new();
+    if let Some(label) = &key.label {
+        parts.push(format!("key {}", label));
+    }
+    match (key.limit, key.limit_remaining) {
+        (Some(limit), Some(remaining)) => {
+            parts.push(format!("${:.2} of ${:.2} left", remaining, limit))
+        }
+        (Some(limit), None) => parts.push(format!("${:.2} used of ${:.2}", key.usage, limit)),
+        (None, _) => parts.push(format!("${:.2} used, no limit", key.usage)),
+    }
+    if key.is_free_tier {
+        parts.push("free tier".to_string());
+    }
+    if let Some(rate) = &key.rate_limit {
+        parts.push(format!("{} requests/{}", rate.requests, rate.interval));
+    }
+    parts.join(" · ")
+}
+
+fn format_latency(elapsed: Duration) -> String {
+    if elapsed.as_secs() >= 1 {
+        format!("{:.1} s", elapsed.as_secs_f64())
+    } else {
+        format!("{} ms", elapsed.as_millis())
+    }
+}
@@ -0,0 +1,114 @@
+use crate::ui::keymap::{Bindings, ThemeAction};
+use agentic_core::theme::{Element, Theme, ThemeVariant};
+use ratatui::{
+    prelude::{Alignment, Constraint, Direction, Frame, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Theme list over the live UI; `theme` is already the highlighted theme
+pub fn render_theme_picker(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    options: &[ThemeVariant],
+    selected: usize,
+    keys: &Bindings<ThemeAction>,
+) {
+    // A theme file that failed to load falls back to the default; say why
+    let warning = theme.load_error().map(|error| format!("⚠ {}", error));
+    let warning_rows = if warning.is_some() { 3 } else { 0 };
+
+    // Options, warning, swatches, instructions, borders and spacing, within
+    // 80% of the screen
+    let width = 46.min(area.width);
+    let wanted = options.len().max(1) as u16 + 6 + warning_rows;
+    let height = wanted.min((area.height * 4 / 5).max(8)).min(area.height);
+    let modal_area = Rect::new(
+        area.x + area.width.saturating_sub(width) / 2,
+        area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    );
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::new()
+        .title(" Theme ")
+        .borders(Borders::ALL)
+        .style(theme.ratatui_style(Element::Active));
+    let inner_area = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),               // Themes
+            Constraint::Length(warning_rows), // Load error
+            Constraint::Length(1),            // Spacing
+            Constraint::Length(1),            // Palette swatches
+            Constraint::Length(1),            // Spacing
+            Constraint::Length(1),            // Instructions
+        ])
+        .split(inner_area);
+
+    // Keep the selection on screen in long lists
+    let rows = usize::from(chunks[0].height.max(1));
+    let first = selected.saturating_sub(rows - 1);
+    let lines: Vec<Line> = options
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(rows)
+        .map(|(index, variant)| {
+            if index == selected {
+                Line::from(Span::styled(
+                    format!("▶ {}", variant.label()),
+                    theme.highlight_style(),
+                ))
+            } else {
+                Line::from(Span::styled(
+                    format!("  {}", variant.label()),
+                    theme.text_style(),
+                ))
+            }
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    if let Some(warning) = warning {
+        let warning = Paragraph::new(warning)
+            .style(theme.ratatui_style(Element::Warning))
+            .wrap(Wrap { trim: true });
+        frame.render_widget(warning, chunks[1]);
+    }
+
+    let colors = theme.colors();
+    let swatches: Vec<Span> = [
+        colors.foreground,
+        colors.accent,
+        colors.secondary,
+        colors.info,
+        colors.warning,
+        colors.border,
+        colors.selection,
+    ]
+    .into_iter()
+    .map(|color| Span::styled("███ ", Style::default().fg(color)))
+    .collect();
+    frame.render_widget(
+        Paragraph::new(Line::from(swatches)).alignment(Alignment::Center),
+        chunks[3],
+    );
+
+    let instructions = Paragraph::new(format!(
+        "[{}{}] Preview | [{}] Apply | [{}] Cancel",
+        keys.label(ThemeAction::Previous),
+        keys.label(ThemeAction::Next),
+        keys.label(ThemeAction::Apply),
+        keys.label(ThemeAction::Cancel),
+    ))
+    .alignment(Alignment::Center)
+    .style(theme.ratatui_style(Element::Inactive));
+    frame.render_widget(instructions, chunks[5]);
+}